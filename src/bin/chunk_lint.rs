@@ -0,0 +1,45 @@
+//! Check the chunks for mistakes, `cargo run --bin chunk_lint [path]`.
+//!
+//! Reads `assets/chunks.ron` by default and exits with an error code when a problem is
+//! found, so it can run in CI.
+
+use std::{env, process};
+
+use cheaters_never_win::{
+    cheat_codes::CheatCodeCatalog,
+    data,
+    platforms::{lint, parse_chunks, CHUNKS_PATH},
+};
+
+fn main() {
+    let (path, bytes) = match env::args().nth(1) {
+        Some(path) => (path.clone(), std::fs::read(&path)),
+        None => (CHUNKS_PATH.to_string(), data::read_data_file(CHUNKS_PATH)),
+    };
+    let bytes = bytes.unwrap_or_else(|err| {
+        eprintln!("Unable to read {}: {}", path, err);
+        process::exit(2);
+    });
+
+    let chunks = parse_chunks(&bytes).unwrap_or_else(|err| {
+        eprintln!("Unable to parse {}: {}", path, err);
+        process::exit(2);
+    });
+    let catalog = CheatCodeCatalog::load();
+
+    let errors = lint::lint_chunks(&chunks, &catalog);
+    for err in errors.iter() {
+        println!("{}", err);
+    }
+
+    if errors.is_empty() {
+        println!(
+            "{}: {} chunks, no problem found",
+            path,
+            chunks.prelude_chunks.len() + chunks.basic_chunks.len() + chunks.cheat_chunks.len()
+        );
+    } else {
+        println!("{}: {} problems found", path, errors.len());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,306 @@
+use std::{collections::HashSet, fmt};
+
+use bevy::prelude::*;
+use ron::de::from_bytes;
+use serde::Deserialize;
+
+use super::{CheatCodeKind, CheatCodeRarity};
+use crate::data;
+
+pub const CATALOG_PATH: &str = "data/cheat_codes.ron";
+const ICONS_PATH: &str = "assets/cheat_codes";
+
+/// Entry of the cheat codes catalog, as written in `data/cheat_codes.ron`
+#[derive(Deserialize, Clone, Debug)]
+pub struct CheatCodeDefinition {
+    pub kind: CheatCodeKind,
+    pub rarity: CheatCodeRarity,
+    #[serde(default)]
+    pub dependencies: Vec<CheatCodeKind>,
+    pub icon: String,
+    pub help_text: String,
+    // defaults to the length of the rarity
+    #[serde(default)]
+    pub code_length: Option<usize>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl CheatCodeDefinition {
+    pub fn code_length(&self) -> usize {
+        self.code_length
+            .unwrap_or_else(|| self.rarity.code_length())
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CheatCodeCatalog {
+    pub codes: Vec<CheatCodeDefinition>,
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Parse(String),
+    DuplicateKind(CheatCodeKind),
+    InvalidLength(CheatCodeKind),
+    UnknownDependency {
+        kind: CheatCodeKind,
+        dependency: CheatCodeKind,
+    },
+    DependencyCycle(Vec<CheatCodeKind>),
+    MissingIcon {
+        kind: CheatCodeKind,
+        icon: String,
+    },
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Parse(err) => write!(f, "unable to parse the catalog: {}", err),
+            CatalogError::DuplicateKind(kind) => write!(f, "[{:?}] is defined twice", kind),
+            CatalogError::InvalidLength(kind) => {
+                write!(f, "[{:?}] code length must be greater than 0", kind)
+            }
+            CatalogError::UnknownDependency { kind, dependency } => write!(
+                f,
+                "[{:?}] depends on [{:?}] which is not an enabled cheat code",
+                kind, dependency
+            ),
+            CatalogError::DependencyCycle(cycle) => {
+                let cycle = cycle
+                    .iter()
+                    .map(|kind| format!("{:?}", kind))
+                    .collect::<Vec<String>>()
+                    .join(" -> ");
+                write!(f, "dependency cycle: {}", cycle)
+            }
+            CatalogError::MissingIcon { kind, icon } => {
+                write!(f, "[{:?}] icon \"{}/{}\" not found", kind, ICONS_PATH, icon)
+            }
+        }
+    }
+}
+
+impl CheatCodeCatalog {
+    /// Load the catalog from the data folder so it can be tuned without recompiling,
+    /// falling back to the version embedded at compile time (wasm or invalid file).
+    pub fn load() -> Self {
+        match data::read_data_file(CATALOG_PATH) {
+            Ok(bytes) => Self::from_bytes_or_embedded(&bytes),
+            Err(err) => {
+                info!(
+                    "[CheatCodesPlugin] {} not readable ({}), using the embedded catalog",
+                    CATALOG_PATH, err
+                );
+                Self::embedded()
+            }
+        }
+    }
+
+    /// Parse a catalog, falling back to the embedded one when it's invalid
+    fn from_bytes_or_embedded(bytes: &[u8]) -> Self {
+        Self::from_bytes(bytes).unwrap_or_else(|errors| {
+            for err in errors.iter() {
+                error!("[CheatCodesPlugin] {}: {}", CATALOG_PATH, err);
+            }
+            warn!("[CheatCodesPlugin] Using the embedded cheat codes catalog");
+            Self::embedded()
+        })
+    }
+
+    fn embedded() -> Self {
+        Self::from_bytes(include_bytes!("../../data/cheat_codes.ron")).unwrap_or_else(|errors| {
+            let errors = errors
+                .iter()
+                .map(|err| err.to_string())
+                .collect::<Vec<String>>()
+                .join("\n");
+            panic!("Invalid embedded cheat codes catalog:\n{}", errors)
+        })
+    }
+
+    /// Parse and validate a catalog
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Vec<CatalogError>> {
+        let catalog = from_bytes::<CheatCodeCatalog>(bytes)
+            .map_err(|err| vec![CatalogError::Parse(err.to_string())])?;
+
+        let errors = catalog.validate();
+        if errors.is_empty() {
+            Ok(catalog)
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn enabled(&self) -> impl Iterator<Item = &CheatCodeDefinition> {
+        self.codes.iter().filter(|code| code.enabled)
+    }
+
+    pub fn get(&self, kind: &CheatCodeKind) -> Option<&CheatCodeDefinition> {
+        self.enabled().find(|code| code.kind == *kind)
+    }
+
+    pub fn validate(&self) -> Vec<CatalogError> {
+        let mut errors = Vec::new();
+
+        // duplicates are checked on the whole file, disabled entries included
+        let mut kinds = HashSet::new();
+        for code in self.codes.iter() {
+            if !kinds.insert(code.kind) {
+                errors.push(CatalogError::DuplicateKind(code.kind));
+            }
+        }
+
+        for code in self.enabled() {
+            if code.code_length() == 0 {
+                errors.push(CatalogError::InvalidLength(code.kind));
+            }
+
+            for dependency in code.dependencies.iter() {
+                if self.get(dependency).is_none() {
+                    errors.push(CatalogError::UnknownDependency {
+                        kind: code.kind,
+                        dependency: *dependency,
+                    });
+                }
+            }
+        }
+
+        if let Some(cycle) = self.find_cycle() {
+            errors.push(CatalogError::DependencyCycle(cycle));
+        }
+
+        // icons are only checked when the assets are reachable (not the case in wasm)
+        let icons_path = data::root_path().join(ICONS_PATH);
+        if icons_path.is_dir() {
+            for code in self.enabled() {
+                if !icons_path.join(&code.icon).is_file() {
+                    errors.push(CatalogError::MissingIcon {
+                        kind: code.kind,
+                        icon: code.icon.clone(),
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Depth first search on the dependency graph, returns the first cycle found
+    fn find_cycle(&self) -> Option<Vec<CheatCodeKind>> {
+        let mut visited = HashSet::new();
+
+        for code in self.enabled() {
+            let mut path = Vec::new();
+            if let Some(cycle) = self.visit(code.kind, &mut path, &mut visited) {
+                return Some(cycle);
+            }
+        }
+
+        None
+    }
+
+    fn visit(
+        &self,
+        kind: CheatCodeKind,
+        path: &mut Vec<CheatCodeKind>,
+        visited: &mut HashSet<CheatCodeKind>,
+    ) -> Option<Vec<CheatCodeKind>> {
+        if let Some(start) = path.iter().position(|k| *k == kind) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(kind);
+            return Some(cycle);
+        }
+
+        if !visited.insert(kind) {
+            return None;
+        }
+
+        path.push(kind);
+        if let Some(code) = self.get(&kind) {
+            for dependency in code.dependencies.iter() {
+                if let Some(cycle) = self.visit(*dependency, path, visited) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every entry uses an icon shipped in the assets, only the tested error shows up
+    fn entry(kind: &str, dependencies: &str, code_length: usize) -> String {
+        format!(
+            "(kind: {}, rarity: Common, dependencies: [{}], icon: \"jump.png\", \
+             help_text: \"\", code_length: Some({}))",
+            kind, dependencies, code_length
+        )
+    }
+
+    fn catalog(entries: &[String]) -> Result<CheatCodeCatalog, Vec<CatalogError>> {
+        CheatCodeCatalog::from_bytes(format!("(codes: [{}])", entries.join(", ")).as_bytes())
+    }
+
+    #[test]
+    fn valid_catalog() {
+        let catalog = catalog(&[entry("Jump", "", 4), entry("DoubleJump", "Jump", 5)]).unwrap();
+        assert_eq!(catalog.enabled().count(), 2);
+        assert_eq!(
+            catalog
+                .get(&CheatCodeKind::DoubleJump)
+                .unwrap()
+                .code_length(),
+            5
+        );
+    }
+
+    #[test]
+    fn duplicate_kind() {
+        let errors = catalog(&[entry("Jump", "", 4), entry("Jump", "", 4)]).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [CatalogError::DuplicateKind(CheatCodeKind::Jump)]
+        ));
+    }
+
+    #[test]
+    fn empty_text() {
+        let errors = catalog(&[entry("Jump", "", 0)]).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [CatalogError::InvalidLength(CheatCodeKind::Jump)]
+        ));
+    }
+
+    #[test]
+    fn unknown_dependency() {
+        let errors = catalog(&[entry("DoubleJump", "Jump", 4)]).unwrap_err();
+        assert!(matches!(
+            errors[..],
+            [CatalogError::UnknownDependency {
+                kind: CheatCodeKind::DoubleJump,
+                dependency: CheatCodeKind::Jump,
+            }]
+        ));
+    }
+
+    #[test]
+    fn fallback_on_parse_error() {
+        let errors = CheatCodeCatalog::from_bytes(b"(codes: [(kind: Jump").unwrap_err();
+        assert!(matches!(errors[..], [CatalogError::Parse(_)]));
+
+        let catalog = CheatCodeCatalog::from_bytes_or_embedded(b"(codes: [(kind: Jump");
+        assert!(catalog.get(&CheatCodeKind::Jump).is_some());
+    }
+}
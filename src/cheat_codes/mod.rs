@@ -0,0 +1,327 @@
+use bevy::prelude::*;
+use rand::distributions::{Alphanumeric, DistString};
+use rand::prelude::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::console::{
+    AddConsoleCommand, CheatHints, ConsoleCommand, ConsoleCommandEvent, PrintToConsoleEvent,
+};
+use crate::controls::ActionInput;
+use crate::run::{NewRunStage, RunSeed};
+
+pub use self::catalog::CheatCodeCatalog;
+
+mod catalog;
+
+pub struct CheatCodesPlugin;
+
+impl Plugin for CheatCodesPlugin {
+    fn build(&self, app: &mut App) {
+        let catalog = CheatCodeCatalog::load();
+        // codes are generated again with the run seed when a run starts
+        app.insert_resource(CheatCodeResource::new(&catalog, &mut rand::thread_rng()));
+        app.insert_resource(catalog);
+        app.add_system_to_stage(NewRunStage, reset_cheat_codes.after("reseed_run"));
+
+        app.add_console_command(ConsoleCommand::new(
+            "codes",
+            "list the cheat codes and what they do",
+        ));
+        app.add_system(codes_command);
+    }
+}
+
+/// Activated codes first, then the ones still to activate with only the letters
+/// revealed by hints
+fn codes_command(
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    cheat_codes_res: Res<CheatCodeResource>,
+    hints: Res<CheatHints>,
+    actions: ActionInput,
+) {
+    for _ in command_reader.iter().filter(|event| event.name == "codes") {
+        let mut codes: Vec<&CheatCode> = cheat_codes_res.codes.values().collect();
+        codes.sort_by_key(|code| (!cheat_codes_res.is_code_activated(&code.kind), code.kind));
+
+        let lines: Vec<String> = codes
+            .iter()
+            .map(|code| {
+                if cheat_codes_res.is_code_activated(&code.kind) {
+                    format!(
+                        "[x] {:?} <{}>: {}",
+                        code.kind,
+                        code.text.to_lowercase(),
+                        actions.fill_prompts(&code.help_text)
+                    )
+                } else {
+                    format!(
+                        "[ ] {:?} <{}>: {}",
+                        code.kind,
+                        hints.masked(&code.kind, &code.text.to_lowercase()),
+                        actions.fill_prompts(&code.help_text)
+                    )
+                }
+            })
+            .collect();
+        print_to_console.send(PrintToConsoleEvent(lines.join("\n")));
+    }
+}
+
+/// Forget the activated codes and generate new code texts
+fn reset_cheat_codes(
+    mut cheat_codes_res: ResMut<CheatCodeResource>,
+    catalog: Res<CheatCodeCatalog>,
+    mut run_seed: ResMut<RunSeed>,
+) {
+    *cheat_codes_res = CheatCodeResource::new(&catalog, &mut run_seed.codes_rng);
+}
+
+// ordered so the candidates of a random pick can be sorted, HashMap order is not reproducible
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Deserialize, Serialize)]
+pub enum CheatCodeKind {
+    // Mandatory
+    Jump,
+
+    // Common
+    Crouch,
+    Attack,
+    AttackDmgBoost,
+    AttackFireRateBoost,
+    MoveLeft,
+    SpeedBoost1,
+    SpeedBoost2,
+    SpeedBoost3,
+    Armor,
+    Dash,
+
+    // Rare
+    DoubleJump,
+    SpeedBoost4,
+    SpeedBoost5,
+    Shield,
+
+    // Legendary
+    ExtraLife,
+    TempInvicibility,
+    Fly,
+}
+
+// here the value is the weight for the weighted distribution
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize)]
+pub enum CheatCodeRarity {
+    Mandatory = 0, // weight of zero because it is not present in the distribution
+    Common = 10,
+    Rare = 5,
+    Legendary = 2,
+}
+
+impl CheatCodeRarity {
+    /// Default length of the generated code, can be overridden in the catalog
+    pub fn code_length(&self) -> usize {
+        match self {
+            CheatCodeRarity::Mandatory => 4,
+            CheatCodeRarity::Common => 4,
+            CheatCodeRarity::Rare => 6,
+            CheatCodeRarity::Legendary => 8,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CheatCodeActivationResult {
+    NotFound,
+    Activated(CheatCodeKind),
+    AlreadyActivated(CheatCodeKind),
+}
+impl CheatCodeActivationResult {
+    pub fn repr(&self) -> String {
+        match self {
+            CheatCodeActivationResult::Activated(kind) => {
+                return format!("[{:?}] cheat code successfully activated", kind)
+            }
+            CheatCodeActivationResult::AlreadyActivated(kind) => {
+                return format!("[{:?}] already activated", kind)
+            }
+            CheatCodeActivationResult::NotFound => {
+                "cheat code not recognized by the system".to_string()
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CheatCode {
+    pub kind: CheatCodeKind,
+    pub rarity: CheatCodeRarity,
+    pub text: String,
+    pub dependencies: Vec<CheatCodeKind>,
+    pub image: String,
+    pub help_text: String,
+}
+
+impl CheatCode {
+    pub fn new(
+        kind: CheatCodeKind,
+        rarity: CheatCodeRarity,
+        text: &str,
+        dependencies: Vec<CheatCodeKind>,
+        image: String,
+        help_text: String,
+    ) -> Self {
+        Self {
+            kind,
+            rarity,
+            text: text.to_string(),
+            dependencies,
+            image,
+            help_text,
+        }
+    }
+}
+
+pub struct CheatCodeResource {
+    pub codes: HashMap<CheatCodeKind, CheatCode>,
+    activated: Vec<CheatCodeKind>,
+}
+
+impl CheatCodeResource {
+    /// Pick the next code the player should unlock, `None` once every code is activated
+    pub fn get_next_code(&self, rng: &mut impl Rng) -> Option<CheatCodeKind> {
+        // first get a list of mandatory cheat codes (JUMP)
+        let mut mandatories = self
+            .codes
+            .iter()
+            .filter(|(kind, code)| {
+                code.rarity == CheatCodeRarity::Mandatory
+                    && !self.is_code_activated(&(*kind).clone())
+            })
+            .map(|(kind, _)| *kind)
+            .collect::<Vec<CheatCodeKind>>();
+        mandatories.sort();
+        // if there is a mandatory code to be chosen, then return it
+        if !mandatories.is_empty() {
+            return mandatories.choose(rng).copied();
+        }
+
+        // then we grab all the codes that haven't been activated yet
+        // don't forget to check for dependencies!
+        let mut available_codes = self
+            .codes
+            .iter()
+            .filter(|(kind, code)| {
+                let missing_deps = code
+                    .dependencies
+                    .iter()
+                    .filter(|kind| !self.is_code_activated(kind))
+                    .count();
+
+                // if the code is not activated and has no missing deps
+                // then it's a potential candidate
+                missing_deps == 0 && !self.is_code_activated(kind)
+            })
+            .map(|(_, code)| code)
+            .collect::<Vec<&CheatCode>>();
+        available_codes.sort_by_key(|code| code.kind);
+
+        // then return a random code based on their rarity (rarity is the weight)
+
+        available_codes
+            .choose_weighted(rng, |code| code.rarity as u8)
+            .ok()
+            .map(|code| code.kind)
+    }
+
+    pub fn activate_code(&mut self, text: &str) -> CheatCodeActivationResult {
+        // iteration over all the existing codes
+        for (_, code) in self.codes.iter() {
+            // it we found a code
+            if code.text.eq(&text.to_lowercase()) {
+                if self.is_code_activated(&code.kind) {
+                    return CheatCodeActivationResult::AlreadyActivated(code.kind);
+                }
+
+                // if the code hasn't been activated do it
+                self.activated.push(code.kind);
+                return CheatCodeActivationResult::Activated(code.kind);
+            }
+        }
+        CheatCodeActivationResult::NotFound
+    }
+
+    pub fn deactivate_code(&mut self, kind: &CheatCodeKind) {
+        if self.is_code_activated(kind) {
+            let index = self.activated.iter().position(|r| r == kind).unwrap();
+            self.activated.remove(index);
+        }
+    }
+
+    pub fn is_code_activated(&self, kind: &CheatCodeKind) -> bool {
+        self.activated.contains(kind)
+    }
+
+    /// Activated codes, in activation order
+    pub fn activated(&self) -> &[CheatCodeKind] {
+        &self.activated
+    }
+
+    pub fn new(catalog: &CheatCodeCatalog, rng: &mut impl Rng) -> Self {
+        let mut codes: HashMap<CheatCodeKind, CheatCode> = HashMap::new();
+
+        for definition in catalog.enabled() {
+            codes.insert(
+                definition.kind,
+                CheatCode::new(
+                    definition.kind,
+                    definition.rarity,
+                    &generate_random_code(definition.code_length(), rng),
+                    definition.dependencies.clone(),
+                    definition.icon.clone(),
+                    definition.help_text.clone(),
+                ),
+            );
+        }
+
+        Self {
+            codes,
+            activated: Vec::new(),
+        }
+    }
+}
+
+pub fn generate_random_code(length: usize, rng: &mut impl Rng) -> String {
+    Alphanumeric.sample_string(rng, length).to_lowercase()
+}
+
+/// Order in which the letters of a code are laid out in a chunk
+pub fn scramble_indices(length: usize, rng: &mut impl Rng) -> Vec<u8> {
+    match length {
+        4 => vec![2, 3, 1, 0],
+        6 => vec![2, 5, 3, 1, 0, 4],
+        8 => vec![4, 2, 6, 3, 1, 7, 0, 5],
+        _ => {
+            let mut indices = (0..length as u8).collect::<Vec<u8>>();
+            indices.shuffle(rng);
+            indices
+        }
+    }
+}
+
+pub fn randomize_text(
+    s: &String,
+    indices: Vec<u8>,
+    is_random_string: bool,
+    rng: &mut impl Rng,
+) -> String {
+    if !is_random_string {
+        let mut result = vec![' '; s.len()];
+        for (i, c) in indices.into_iter().zip(s.chars()) {
+            result[i as usize] = c;
+        }
+        return result.into_iter().collect();
+    } else {
+        return Alphanumeric.sample_string(rng, s.len()).to_lowercase();
+    }
+}
@@ -0,0 +1,653 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_parallax::ParallaxCameraComponent;
+use ron::ser::{to_string_pretty, PrettyConfig};
+
+use crate::{
+    cheat_codes::CheatCodeCatalog,
+    data,
+    enemies::EnemyKind,
+    interactables::InteractableComponent,
+    physics,
+    platforms::{
+        lint, platform::PlatformKind, CharData, Chunk, ChunksResource, EnemyData, PlatformData,
+    },
+    run::NewRunEvent,
+    states::GameStates,
+};
+
+const EXPORT_PATH: &str = "data/exported_chunk.ron";
+const GRID_SIZES: [f32; 6] = [1.0, 2.0, 5.0, 10.0, 20.0, 40.0];
+const CAMERA_SPEED: f32 = 800.0;
+// where the player spawns when previewing, see `spawn_character`
+const PLAYER_SPAWN: [f32; 2] = [0.0, 300.0];
+// click distance to select an enemy, a terminal or a letter slot
+const PICK_RADIUS: f32 = 20.0;
+
+/// Spawned by the editor, despawned when leaving it
+#[derive(Component)]
+pub struct EditorEntity;
+
+/// Drawing of an element of the edited chunk, redrawn when the chunk changes
+#[derive(Component)]
+pub struct EditorItemSprite;
+
+/// Ghost of the selected tool under the mouse
+#[derive(Component)]
+pub struct EditorCursor;
+
+#[derive(Component)]
+pub struct EditorHelpText;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EditorTool {
+    Platform(PlatformKind),
+    Enemy(EnemyKind),
+    Terminal,
+    CharSlot,
+}
+
+impl EditorTool {
+    fn size(&self) -> Vec2 {
+        match self {
+            EditorTool::Platform(kind) => kind.size(),
+            EditorTool::Enemy(_) => Vec2::new(26.0, 26.0),
+            EditorTool::Terminal => Vec2::new(40.0, 60.0),
+            EditorTool::CharSlot => Vec2::new(20.0, 20.0),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            EditorTool::Platform(_) => Color::rgb_u8(120, 120, 140),
+            EditorTool::Enemy(_) => Color::rgb_u8(220, 60, 60),
+            EditorTool::Terminal => Color::rgb_u8(60, 120, 220),
+            EditorTool::CharSlot => Color::rgb_u8(230, 200, 60),
+        }
+    }
+}
+
+/// Tools selected with the number keys, in order
+fn tools() -> Vec<EditorTool> {
+    vec![
+        EditorTool::Platform(PlatformKind::Platform1),
+        EditorTool::Platform(PlatformKind::Platform2),
+        EditorTool::Platform(PlatformKind::Platform3),
+        EditorTool::Platform(PlatformKind::Platform4),
+        EditorTool::Platform(PlatformKind::Platform5),
+        EditorTool::Platform(PlatformKind::Platform6),
+        EditorTool::Enemy(EnemyKind::Slime),
+        EditorTool::Terminal,
+        EditorTool::CharSlot,
+    ]
+}
+
+const TOOL_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+/// Chunk being edited, kept while it's previewed
+pub struct ChunkEditor {
+    pub chunk: Chunk,
+    pub tool: EditorTool,
+    grid_index: usize,
+    // prelude chunks put aside while the edited chunk is previewed
+    saved_prelude: Option<Vec<Chunk>>,
+    status: String,
+    dirty: bool,
+}
+
+impl ChunkEditor {
+    pub fn new() -> Self {
+        Self {
+            chunk: empty_chunk(),
+            tool: EditorTool::Platform(PlatformKind::Platform1),
+            grid_index: 3,
+            saved_prelude: None,
+            status: String::new(),
+            dirty: true,
+        }
+    }
+
+    fn grid_size(&self) -> f32 {
+        GRID_SIZES[self.grid_index]
+    }
+
+    /// The edited chunk with the offsets computed like the hand written chunks: it
+    /// starts 1 pixel before its leftmost platform and the next chunk 1 pixel after
+    /// its rightmost platform
+    pub fn exported_chunk(&self) -> Chunk {
+        let mut chunk = self.chunk.clone();
+        if let Some((left, right)) = platforms_span(&chunk) {
+            chunk.chunk_offset = 1.0 - left;
+            chunk.next_chunk_offset = chunk.chunk_offset + right + 1.0;
+        }
+        chunk
+    }
+
+    /// Prelude chunks are spawned at the origin, without their chunk offset
+    fn preview_chunk(&self) -> Chunk {
+        let mut chunk = self.chunk.clone();
+        chunk.chunk_offset = 0.0;
+        chunk.next_chunk_offset = platforms_span(&chunk).map_or(1.0, |(_, right)| right + 1.0);
+        chunk
+    }
+
+    /// Remove the element under `position`, platforms last as the others are drawn
+    /// on top of them
+    fn remove_at(&mut self, position: Vec2) -> bool {
+        let near = |other: &Vec2| other.distance(position) <= PICK_RADIUS;
+
+        if let Some(i) = self.chunk.enemies.iter().position(|e| near(&e.position)) {
+            self.chunk.enemies.remove(i);
+            return true;
+        }
+        if let Some(i) = self.chunk.terminals.iter().position(near) {
+            self.chunk.terminals.remove(i);
+            return true;
+        }
+        for char_data in self.chunk.chars.iter_mut() {
+            if let Some(i) = char_data.positions.iter().position(near) {
+                char_data.positions.remove(i);
+                return true;
+            }
+        }
+        // last placed platform first
+        if let Some(i) = self.chunk.platforms.iter().rposition(|platform| {
+            let half_size = platform.platform_kind.size() / 2.0;
+            (position - platform.position).abs().cmple(half_size).all()
+        }) {
+            self.chunk.platforms.remove(i);
+            return true;
+        }
+        false
+    }
+
+    fn place(&mut self, position: Vec2) {
+        match &self.tool {
+            EditorTool::Platform(kind) => self.chunk.platforms.push(PlatformData {
+                platform_kind: kind.clone(),
+                position,
+            }),
+            EditorTool::Enemy(kind) => self.chunk.enemies.push(EnemyData {
+                enemy_kind: kind.clone(),
+                position,
+            }),
+            EditorTool::Terminal => self.chunk.terminals.push(position),
+            EditorTool::CharSlot => self.chunk.chars[0].positions.push(position),
+        }
+    }
+}
+
+fn empty_chunk() -> Chunk {
+    Chunk {
+        // slots for the seeded and random letters
+        chars: vec![CharData {
+            cheat_kind: None,
+            positions: Vec::new(),
+            is_random: false,
+        }],
+        ..Default::default()
+    }
+}
+
+fn platforms_span(chunk: &Chunk) -> Option<(f32, f32)> {
+    if chunk.platforms.is_empty() {
+        return None;
+    }
+
+    let edges = chunk.platforms.iter().map(|platform| {
+        let half_width = platform.platform_kind.size().x / 2.0;
+        (
+            platform.position.x - half_width,
+            platform.position.x + half_width,
+        )
+    });
+    let left = edges.clone().map(|e| e.0).fold(f32::INFINITY, f32::min);
+    let right = edges.map(|e| e.1).fold(f32::NEG_INFINITY, f32::max);
+    Some((left, right))
+}
+
+/// Level editor to place platforms, enemies, terminals and letters on a grid, only in
+/// debug builds. Opened with F2 from the main menu.
+pub struct ChunkEditorPlugin;
+
+impl Plugin for ChunkEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ChunkEditor::new());
+
+        app.add_system_set(SystemSet::on_update(GameStates::MainMenu).with_system(open_editor));
+        app.add_system(return_to_editor);
+
+        app.add_system_set(
+            SystemSet::on_enter(GameStates::ChunkEditor)
+                .with_system(setup_editor)
+                .with_system(despawn_preview_interactables),
+        );
+        app.add_system_set(
+            SystemSet::on_update(GameStates::ChunkEditor)
+                .with_system(select_tool)
+                .with_system(move_camera)
+                .with_system(edit_chunk.label("edit_chunk"))
+                .with_system(editor_commands.label("edit_chunk"))
+                .with_system(redraw_chunk.after("edit_chunk"))
+                .with_system(update_cursor.after("edit_chunk")),
+        );
+        app.add_system_set(SystemSet::on_exit(GameStates::ChunkEditor).with_system(destroy_editor));
+    }
+}
+
+fn open_editor(keyboard: Res<Input<KeyCode>>, mut game_state: ResMut<State<GameStates>>) {
+    if keyboard.just_pressed(KeyCode::F2) {
+        info!("[ChunkEditorPlugin] Opening the chunk editor");
+        game_state.set(GameStates::ChunkEditor).unwrap();
+    }
+}
+
+/// F2 goes back to the editor from anywhere while previewing
+fn return_to_editor(
+    keyboard: Res<Input<KeyCode>>,
+    mut editor: ResMut<ChunkEditor>,
+    mut chunks_resource: ResMut<ChunksResource>,
+    mut game_state: ResMut<State<GameStates>>,
+) {
+    if !keyboard.just_pressed(KeyCode::F2) || *game_state.current() == GameStates::ChunkEditor {
+        return;
+    }
+
+    if let Some(prelude_chunks) = editor.saved_prelude.take() {
+        chunks_resource.prelude_chunks = prelude_chunks;
+        // replacing the stack cleans the preview run like a new run does
+        if let Err(err) = game_state.replace(GameStates::ChunkEditor) {
+            error!("[ChunkEditorPlugin] Unable to leave the preview: {:?}", err);
+        }
+    }
+}
+
+fn setup_editor(
+    mut commands: Commands,
+    mut editor: ResMut<ChunkEditor>,
+    asset_server: Res<AssetServer>,
+    mut camera_query: Query<&mut Transform, With<ParallaxCameraComponent>>,
+) {
+    info!("[ChunkEditorPlugin] Building the editor");
+    editor.dirty = true;
+
+    for mut transform in camera_query.iter_mut() {
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+    }
+
+    // grid, every 80 pixels, brighter at the origin of the chunk
+    let line_color = |value: f32| {
+        if value == 0.0 {
+            Color::rgba(1.0, 1.0, 1.0, 0.4)
+        } else {
+            Color::rgba(1.0, 1.0, 1.0, 0.1)
+        }
+    };
+    for i in -16..=48 {
+        let x = i as f32 * 80.0;
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: line_color(x),
+                    custom_size: Some(Vec2::new(1.0, 1440.0)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(x, 0.0, 1.0),
+                ..Default::default()
+            })
+            .insert(EditorEntity);
+    }
+    for i in -9..=9 {
+        let y = i as f32 * 80.0;
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: line_color(y),
+                    custom_size: Some(Vec2::new(5120.0, 1.0)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(1280.0, y, 1.0),
+                ..Default::default()
+            })
+            .insert(EditorEntity);
+    }
+
+    // player spawn when previewing
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0.4, 1.0, 0.4, 0.5),
+                custom_size: Some(Vec2::new(physics::PLAYER_WIDTH, physics::PLAYER_HEIGHT)),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(Vec2::from(PLAYER_SPAWN).extend(2.0)),
+            ..Default::default()
+        })
+        .insert(EditorEntity);
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            transform: Transform::from_xyz(0.0, 0.0, 10.0),
+            ..Default::default()
+        })
+        .insert(EditorCursor)
+        .insert(EditorEntity);
+
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(10.0),
+                    top: Val::Px(10.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/VT323-Regular.ttf"),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(EditorHelpText)
+        .insert(EditorEntity);
+}
+
+/// Letters and terminals of the preview aren't rigid bodies, they survive the run
+fn despawn_preview_interactables(
+    mut commands: Commands,
+    query: Query<Entity, With<InteractableComponent>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn destroy_editor(mut commands: Commands, query: Query<Entity, With<EditorEntity>>) {
+    info!("[ChunkEditorPlugin] Destroying state entities before exiting...");
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn select_tool(keyboard: Res<Input<KeyCode>>, mut editor: ResMut<ChunkEditor>) {
+    for (key, tool) in TOOL_KEYS.iter().zip(tools()) {
+        if keyboard.just_pressed(*key) {
+            editor.tool = tool;
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::LBracket) && editor.grid_index > 0 {
+        editor.grid_index -= 1;
+    }
+    if keyboard.just_pressed(KeyCode::RBracket) && editor.grid_index < GRID_SIZES.len() - 1 {
+        editor.grid_index += 1;
+    }
+}
+
+fn move_camera(
+    keyboard: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut camera_query: Query<&mut Transform, With<ParallaxCameraComponent>>,
+) {
+    // Ctrl+S exports the chunk
+    if keyboard.pressed(KeyCode::LControl) || keyboard.pressed(KeyCode::RControl) {
+        return;
+    }
+
+    let mut direction = Vec2::ZERO;
+    if keyboard.pressed(KeyCode::A) || keyboard.pressed(KeyCode::Left) {
+        direction.x -= 1.0;
+    }
+    if keyboard.pressed(KeyCode::D) || keyboard.pressed(KeyCode::Right) {
+        direction.x += 1.0;
+    }
+    if keyboard.pressed(KeyCode::S) || keyboard.pressed(KeyCode::Down) {
+        direction.y -= 1.0;
+    }
+    if keyboard.pressed(KeyCode::W) || keyboard.pressed(KeyCode::Up) {
+        direction.y += 1.0;
+    }
+
+    for mut transform in camera_query.iter_mut() {
+        transform.translation += (direction * CAMERA_SPEED * time.delta_seconds()).extend(0.0);
+    }
+}
+
+/// Position under the mouse snapped to the grid, in chunk coordinates
+fn cursor_position(
+    windows: &Windows,
+    camera_query: &Query<&Transform, With<ParallaxCameraComponent>>,
+    grid_size: f32,
+) -> Option<Vec2> {
+    let window = windows.get_primary()?;
+    let cursor = window.cursor_position()?;
+    let camera = camera_query.get_single().ok()?;
+
+    let world =
+        cursor - Vec2::new(window.width(), window.height()) / 2.0 + camera.translation.truncate();
+    Some((world / grid_size).round() * grid_size)
+}
+
+fn edit_chunk(
+    mouse: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    camera_query: Query<&Transform, With<ParallaxCameraComponent>>,
+    mut editor: ResMut<ChunkEditor>,
+) {
+    let position = match cursor_position(&windows, &camera_query, editor.grid_size()) {
+        Some(position) => position,
+        None => return,
+    };
+
+    if mouse.just_pressed(MouseButton::Left) {
+        editor.place(position);
+        editor.dirty = true;
+    } else if mouse.just_pressed(MouseButton::Right) && editor.remove_at(position) {
+        editor.dirty = true;
+    }
+}
+
+fn editor_commands(
+    keyboard: Res<Input<KeyCode>>,
+    mut editor: ResMut<ChunkEditor>,
+    mut chunks_resource: ResMut<ChunksResource>,
+    catalog: Res<CheatCodeCatalog>,
+    mut new_run_writer: EventWriter<NewRunEvent>,
+    mut game_state: ResMut<State<GameStates>>,
+) {
+    let ctrl = keyboard.pressed(KeyCode::LControl) || keyboard.pressed(KeyCode::RControl);
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        game_state.set(GameStates::MainMenu).unwrap();
+    } else if keyboard.just_pressed(KeyCode::N) {
+        editor.chunk = empty_chunk();
+        editor.status = "New chunk".to_string();
+        editor.dirty = true;
+    } else if keyboard.just_pressed(KeyCode::P) {
+        if editor.chunk.platforms.is_empty() {
+            editor.status = "Place a platform before previewing".to_string();
+            return;
+        }
+        // the edited chunk is the only prelude chunk until F2 is pressed
+        let prelude_chunks = std::mem::replace(
+            &mut chunks_resource.prelude_chunks,
+            vec![editor.preview_chunk()],
+        );
+        editor.saved_prelude = Some(prelude_chunks);
+        new_run_writer.send(NewRunEvent { seed: None });
+    } else if ctrl && keyboard.just_pressed(KeyCode::S) {
+        editor.status = export_chunk(&editor.exported_chunk(), &catalog);
+    }
+}
+
+/// Write the chunk to `EXPORT_PATH`, ready to be pasted in `assets/chunks.ron`
+fn export_chunk(chunk: &Chunk, catalog: &CheatCodeCatalog) -> String {
+    let path = data::root_path().join(EXPORT_PATH);
+    let result = to_string_pretty(chunk, PrettyConfig::new())
+        .map_err(|err| err.to_string())
+        .and_then(|contents| {
+            data::write_user_file(&path, &contents).map_err(|err| err.to_string())
+        });
+    if let Err(err) = result {
+        error!(
+            "[ChunkEditorPlugin] Unable to export the chunk to {}: {}",
+            path.display(),
+            err
+        );
+        return format!("Export failed: {}", err);
+    }
+    info!("[ChunkEditorPlugin] Chunk exported to {}", path.display());
+
+    let chunks = ChunksResource {
+        prelude_chunks: Vec::new(),
+        basic_chunks: vec![chunk.clone()],
+        cheat_chunks: Vec::new(),
+        furthest_x: 0.0,
+        introduced_cheats: HashSet::new(),
+        last_chunk: None,
+    };
+    let errors = lint::lint_chunks(&chunks, catalog);
+    for err in errors.iter() {
+        warn!("[ChunkEditorPlugin] {}", err);
+    }
+
+    if errors.is_empty() {
+        format!("Exported to {}", EXPORT_PATH)
+    } else {
+        format!(
+            "Exported to {}, {} problems found (see the log)",
+            EXPORT_PATH,
+            errors.len()
+        )
+    }
+}
+
+fn item_sprite(tool: &EditorTool, position: Vec2, z: f32) -> SpriteBundle {
+    SpriteBundle {
+        sprite: Sprite {
+            color: tool.color(),
+            custom_size: Some(tool.size()),
+            ..Default::default()
+        },
+        transform: Transform::from_translation(position.extend(z)),
+        ..Default::default()
+    }
+}
+
+fn redraw_chunk(
+    mut commands: Commands,
+    mut editor: ResMut<ChunkEditor>,
+    sprite_query: Query<Entity, With<EditorItemSprite>>,
+) {
+    if !editor.dirty {
+        return;
+    }
+    editor.dirty = false;
+
+    for entity in sprite_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let chunk = &editor.chunk;
+    let mut items: Vec<(EditorTool, Vec2, f32)> = Vec::new();
+    items.extend(chunk.platforms.iter().map(|platform| {
+        (
+            EditorTool::Platform(platform.platform_kind.clone()),
+            platform.position,
+            3.0,
+        )
+    }));
+    items.extend(chunk.enemies.iter().map(|enemy| {
+        (
+            EditorTool::Enemy(enemy.enemy_kind.clone()),
+            enemy.position,
+            4.0,
+        )
+    }));
+    items.extend(
+        chunk
+            .terminals
+            .iter()
+            .map(|position| (EditorTool::Terminal, *position, 4.0)),
+    );
+    items.extend(
+        chunk
+            .chars
+            .iter()
+            .flat_map(|char_data| char_data.positions.iter())
+            .map(|position| (EditorTool::CharSlot, *position, 5.0)),
+    );
+
+    for (tool, position, z) in items {
+        commands
+            .spawn_bundle(item_sprite(&tool, position, z))
+            .insert(EditorItemSprite)
+            .insert(EditorEntity);
+    }
+}
+
+fn update_cursor(
+    editor: Res<ChunkEditor>,
+    windows: Res<Windows>,
+    camera_query: Query<&Transform, With<ParallaxCameraComponent>>,
+    mut cursor_query: Query<
+        (&mut Sprite, &mut Transform, &mut Visibility),
+        (With<EditorCursor>, Without<ParallaxCameraComponent>),
+    >,
+    mut text_query: Query<&mut Text, With<EditorHelpText>>,
+) {
+    let position = cursor_position(&windows, &camera_query, editor.grid_size());
+
+    if let Ok((mut sprite, mut transform, mut visibility)) = cursor_query.get_single_mut() {
+        visibility.is_visible = position.is_some();
+        if let Some(position) = position {
+            let color = editor.tool.color();
+            sprite.color = Color::rgba(color.r(), color.g(), color.b(), 0.5);
+            sprite.custom_size = Some(editor.tool.size());
+            transform.translation = position.extend(transform.translation.z);
+        }
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        let tool = match &editor.tool {
+            EditorTool::Platform(kind) => format!("{:?}", kind),
+            EditorTool::Enemy(kind) => format!("{:?}", kind),
+            EditorTool::Terminal => "Terminal".to_string(),
+            EditorTool::CharSlot => "Letter slot".to_string(),
+        };
+        let position = position.map_or("-".to_string(), |p| format!("({}, {})", p.x, p.y));
+        text.sections[0].value = [
+            "CHUNK EDITOR".to_string(),
+            format!(
+                "Tool: {} (1-6 platforms, 7 slime, 8 terminal, 9 letter slot)",
+                tool
+            ),
+            format!("Grid: {} px ([ and ] to change)", editor.grid_size()),
+            format!("Cursor: {}", position),
+            "Left click: place, right click: remove, WASD or arrows: move the view".to_string(),
+            "P: preview (F2 to come back), Ctrl+S: export, N: new chunk, Esc: main menu"
+                .to_string(),
+            editor.status.clone(),
+        ]
+        .join("\n");
+    }
+}
@@ -0,0 +1,289 @@
+use std::fmt;
+
+/// Type of a command argument, checked before the command is dispatched
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArgKind {
+    Text,
+    Integer,
+    Number,
+    // every remaining token joined with spaces, must be the last argument
+    Rest,
+}
+
+impl ArgKind {
+    fn expected(&self) -> &'static str {
+        match self {
+            ArgKind::Text | ArgKind::Rest => "a text",
+            ArgKind::Integer => "an integer",
+            ArgKind::Number => "a number",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub optional: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ArgValue {
+    Text(String),
+    Integer(i64),
+    Number(f32),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ArgError {
+    UnterminatedQuote,
+    Missing(&'static str),
+    Extra(String),
+    Invalid {
+        name: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgError::UnterminatedQuote => write!(f, "missing closing quote"),
+            ArgError::Missing(name) => write!(f, "missing argument <{}>", name),
+            ArgError::Extra(value) => write!(f, "unexpected argument \"{}\"", value),
+            ArgError::Invalid {
+                name,
+                value,
+                expected,
+            } => write!(f, "<{}> must be {}, got \"{}\"", name, expected, value),
+        }
+    }
+}
+
+/// Split a command line on spaces, text between single or double quotes is kept whole
+/// and `\` escapes the next character
+pub fn tokenize(input: &str) -> Result<Vec<String>, ArgError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    // a quoted empty string is still a token
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+                in_token = true;
+            }
+            (c, Some(open)) if c == open => quote = None,
+            (c, Some(_)) => current.push(c),
+            ('"', None) | ('\'', None) => {
+                quote = Some(c);
+                in_token = true;
+            }
+            (c, None) if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            (c, None) => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err(ArgError::UnterminatedQuote);
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Split a line on the `;` outside of quotes, empty commands are dropped
+pub fn split_commands(line: &str) -> Result<Vec<String>, ArgError> {
+    let mut commands = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            // escaped characters are kept for the tokenizer
+            ('\\', _) => {
+                current.push(c);
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            (';', None) => commands.push(std::mem::take(&mut current)),
+            (c, Some(open)) if c == open => {
+                quote = None;
+                current.push(c);
+            }
+            ('"', None) | ('\'', None) => {
+                quote = Some(c);
+                current.push(c);
+            }
+            (c, _) => current.push(c),
+        }
+    }
+
+    if quote.is_some() {
+        return Err(ArgError::UnterminatedQuote);
+    }
+    commands.push(current);
+
+    Ok(commands
+        .into_iter()
+        .map(|command| command.trim().to_string())
+        .filter(|command| !command.is_empty())
+        .collect())
+}
+
+/// Check the tokens against the specs, optional arguments come last
+pub fn parse_args(specs: &[ArgSpec], tokens: &[String]) -> Result<Vec<ArgValue>, ArgError> {
+    let takes_rest = specs.last().map(|spec| spec.kind) == Some(ArgKind::Rest);
+    if let Some(extra) = tokens.get(specs.len()) {
+        if !takes_rest {
+            return Err(ArgError::Extra(extra.clone()));
+        }
+    }
+
+    let mut values = Vec::new();
+    for (i, spec) in specs.iter().enumerate() {
+        let token = match tokens.get(i) {
+            Some(token) => token,
+            None if spec.optional => break,
+            None => return Err(ArgError::Missing(spec.name)),
+        };
+
+        let invalid = || ArgError::Invalid {
+            name: spec.name,
+            value: token.clone(),
+            expected: spec.kind.expected(),
+        };
+        values.push(match spec.kind {
+            ArgKind::Text => ArgValue::Text(token.clone()),
+            ArgKind::Rest => ArgValue::Text(tokens[i..].join(" ")),
+            ArgKind::Integer => ArgValue::Integer(token.parse().map_err(|_| invalid())?),
+            ArgKind::Number => ArgValue::Number(token.parse().map_err(|_| invalid())?),
+        });
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn spec(name: &'static str, kind: ArgKind, optional: bool) -> ArgSpec {
+        ArgSpec {
+            name,
+            kind,
+            optional,
+        }
+    }
+
+    #[test]
+    fn tokenize_empty_input() {
+        assert_eq!(tokenize(""), Ok(Vec::new()));
+        assert_eq!(tokenize("   "), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn tokenize_quotes_and_escapes() {
+        assert_eq!(
+            tokenize(r#"echo "hello world" 'it''s' \"a\ b"#),
+            Ok(tokens(&["echo", "hello world", "its", "\"a b"]))
+        );
+        assert_eq!(tokenize(r#"set x """#), Ok(tokens(&["set", "x", ""])));
+    }
+
+    #[test]
+    fn tokenize_unterminated_quote() {
+        assert_eq!(tokenize("echo \"hello"), Err(ArgError::UnterminatedQuote));
+        assert_eq!(tokenize("echo 'hello"), Err(ArgError::UnterminatedQuote));
+    }
+
+    #[test]
+    fn split_commands_on_semicolons() {
+        assert_eq!(
+            split_commands("help; echo \"a;b\" ;; clear"),
+            Ok(tokens(&["help", "echo \"a;b\"", "clear"]))
+        );
+        assert_eq!(split_commands(""), Ok(Vec::new()));
+        assert_eq!(split_commands(" ; "), Ok(Vec::new()));
+        assert_eq!(
+            split_commands("echo 'a; clear"),
+            Err(ArgError::UnterminatedQuote)
+        );
+    }
+
+    #[test]
+    fn parse_args_missing_and_extra() {
+        let specs = [spec("code", ArgKind::Text, false)];
+        assert_eq!(parse_args(&specs, &[]), Err(ArgError::Missing("code")));
+        assert_eq!(
+            parse_args(&specs, &tokens(&["jump", "now"])),
+            Err(ArgError::Extra("now".to_string()))
+        );
+        assert_eq!(
+            parse_args(&[], &tokens(&["now"])),
+            Err(ArgError::Extra("now".to_string()))
+        );
+        assert_eq!(
+            parse_args(&specs, &tokens(&["jump"])),
+            Ok(vec![ArgValue::Text("jump".to_string())])
+        );
+    }
+
+    #[test]
+    fn parse_args_optional_and_rest() {
+        let specs = [spec("text", ArgKind::Rest, true)];
+        assert_eq!(parse_args(&specs, &[]), Ok(Vec::new()));
+        assert_eq!(
+            parse_args(&specs, &tokens(&["hello", "world"])),
+            Ok(vec![ArgValue::Text("hello world".to_string())])
+        );
+    }
+
+    #[test]
+    fn parse_args_wrong_types() {
+        let specs = [
+            spec("count", ArgKind::Integer, false),
+            spec("scale", ArgKind::Number, false),
+        ];
+        assert_eq!(
+            parse_args(&specs, &tokens(&["3", "0.5"])),
+            Ok(vec![ArgValue::Integer(3), ArgValue::Number(0.5)])
+        );
+        assert_eq!(
+            parse_args(&specs, &tokens(&["three", "0.5"])),
+            Err(ArgError::Invalid {
+                name: "count",
+                value: "three".to_string(),
+                expected: "an integer",
+            })
+        );
+        assert_eq!(
+            parse_args(&specs, &tokens(&["3", "fast"])),
+            Err(ArgError::Invalid {
+                name: "scale",
+                value: "fast".to_string(),
+                expected: "a number",
+            })
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::{event::PrintToConsoleEvent, registry::ConsoleCommandEvent, CheatCodeActivatedEvent};
+use crate::audio::{GameAudioOptions, GameAudioState};
+use crate::cheat_codes::{CheatCodeActivationResult, CheatCodeKind, CheatCodeResource};
+use crate::platforms::CodeSeedingResource;
+use crate::run::RunSeed;
+use crate::runner::CollectedChars;
+
+/// Outcome of typing a code, checked before any letter is spent
+#[derive(Debug, PartialEq)]
+pub enum CheatAttempt {
+    // letters spent, the code can be activated
+    Paid,
+    Unknown { suggestion: Option<Suggestion> },
+    AlreadyActivated(CheatCodeKind),
+    MissingLetters(Vec<char>),
+}
+
+/// Code close to a mistyped one, the text is only given once the player found it
+#[derive(Debug, PartialEq)]
+pub enum Suggestion {
+    Activated(String),
+    NotActivated { kind: CheatCodeKind, wrong: usize },
+}
+
+/// Number of revealed letters of each code, bought with the `hint` command
+pub struct CheatHints {
+    pub revealed: HashMap<CheatCodeKind, usize>,
+}
+
+impl CheatHints {
+    pub fn new() -> Self {
+        Self {
+            revealed: HashMap::new(),
+        }
+    }
+
+    /// Code text with the letters not revealed yet replaced by `_`
+    pub fn masked(&self, kind: &CheatCodeKind, text: &str) -> String {
+        let revealed = self.revealed.get(kind).copied().unwrap_or(0);
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| if i < revealed { c } else { '_' })
+            .collect()
+    }
+}
+
+pub fn reset_cheat_hints(mut hints: ResMut<CheatHints>) {
+    hints.revealed.clear();
+}
+
+/// Letters of `text` not in the collected letters, with repetitions
+fn missing_letters(text: &str, collected_chars: &CollectedChars) -> Vec<char> {
+    let mut available = collected_chars.values_map.clone();
+    let mut missing = Vec::new();
+    for c in text.chars() {
+        match available.get_mut(&c) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => missing.push(c),
+        }
+    }
+    missing.sort_unstable();
+    missing
+}
+
+fn spend_letter(collected_chars: &mut CollectedChars, c: char) {
+    if let Some(index) = collected_chars.values.iter().position(|val| *val == c) {
+        collected_chars.values.remove(index);
+    }
+    if let Some(count) = collected_chars.values_map.get_mut(&c) {
+        *count = count.saturating_sub(1);
+    }
+}
+
+/// Number of single character edits to go from `a` to `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + (ca != *cb) as usize;
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Closest code, as long as less than a third of it is wrong
+fn closest_code(text: &str, cheat_codes: &CheatCodeResource) -> Option<Suggestion> {
+    let (wrong, code) = cheat_codes
+        .codes
+        .values()
+        .map(|code| (edit_distance(text, &code.text), code))
+        .filter(|(distance, code)| *distance <= (code.text.len() / 3).max(1))
+        .min_by_key(|(distance, code)| (*distance, code.kind))?;
+
+    Some(if cheat_codes.is_code_activated(&code.kind) {
+        Suggestion::Activated(code.text.clone())
+    } else {
+        Suggestion::NotActivated {
+            kind: code.kind,
+            wrong,
+        }
+    })
+}
+
+/// Spend the letters of the typed code if the player has all of them
+pub fn try_cheat(
+    collected_chars: &mut CollectedChars,
+    code_text: &str,
+    cheat_codes: &CheatCodeResource,
+) -> CheatAttempt {
+    let code = match cheat_codes
+        .codes
+        .values()
+        .find(|code| code.text == code_text)
+    {
+        Some(code) => code,
+        None => {
+            return CheatAttempt::Unknown {
+                suggestion: closest_code(code_text, cheat_codes),
+            }
+        }
+    };
+
+    if cheat_codes.is_code_activated(&code.kind) {
+        return CheatAttempt::AlreadyActivated(code.kind);
+    }
+
+    let missing = missing_letters(&code.text, collected_chars);
+    if !missing.is_empty() {
+        return CheatAttempt::MissingLetters(missing);
+    }
+
+    for c in code.text.chars() {
+        spend_letter(collected_chars, c);
+    }
+    CheatAttempt::Paid
+}
+
+pub fn run_cheat_commands(
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    mut cheat_codes_res: ResMut<CheatCodeResource>,
+    mut collected_chars: ResMut<CollectedChars>,
+    mut ev_writer: EventWriter<CheatCodeActivatedEvent>,
+    mut game_audio_state: ResMut<GameAudioState>,
+    mut hints: ResMut<CheatHints>,
+    mut code_seeding: ResMut<CodeSeedingResource>,
+    mut run_seed: ResMut<RunSeed>,
+) {
+    for event in command_reader.iter() {
+        match event.name.as_str() {
+            "cheat" => {
+                // checked by the parser
+                let code_text = event.text(0).unwrap_or_default().to_lowercase();
+
+                print_to_console.send(PrintToConsoleEvent(format!(
+                    "Activating cheat code: <{}>...",
+                    code_text
+                )));
+
+                let message = match try_cheat(&mut collected_chars, &code_text, &cheat_codes_res) {
+                    CheatAttempt::Paid => {
+                        let activation_res = cheat_codes_res.activate_code(&code_text);
+                        if let CheatCodeActivationResult::Activated(kind) = activation_res {
+                            ev_writer.send(CheatCodeActivatedEvent(kind));
+                            game_audio_state.queue_sound(
+                                "powerup-sound".to_owned(),
+                                GameAudioOptions {
+                                    ..Default::default()
+                                },
+                            );
+                        }
+                        format!("Activation result: {}", activation_res.repr())
+                    }
+                    CheatAttempt::AlreadyActivated(kind) => {
+                        format!("[{:?}] already activated, no letter was used.", kind)
+                    }
+                    CheatAttempt::MissingLetters(missing) => format!(
+                        "Failed to activate. Missing letters: {}",
+                        missing
+                            .iter()
+                            .map(|c| c.to_string())
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    ),
+                    CheatAttempt::Unknown {
+                        suggestion: Some(Suggestion::Activated(text)),
+                    } => format!(
+                        "Cheat code not recognized by the system. Did you mean <{}>?",
+                        text
+                    ),
+                    CheatAttempt::Unknown {
+                        suggestion: Some(Suggestion::NotActivated { kind, wrong }),
+                    } => format!(
+                        "Cheat code not recognized by the system. Close to the [{:?}] code, {} letter(s) wrong.",
+                        kind, wrong
+                    ),
+                    CheatAttempt::Unknown { suggestion: None } => {
+                        "Cheat code not recognized by the system.".to_string()
+                    }
+                };
+                print_to_console.send(PrintToConsoleEvent(message));
+            }
+            "hint" => {
+                // same code as the one whose letters are placed in the level
+                let kind =
+                    match code_seeding.update_target(&cheat_codes_res, &mut run_seed.codes_rng) {
+                        Some(kind) => kind,
+                        None => {
+                            print_to_console.send(PrintToConsoleEvent(
+                                "Every cheat code is already activated.".to_string(),
+                            ));
+                            continue;
+                        }
+                    };
+                let code_text = cheat_codes_res.codes[&kind].text.clone();
+                let revealed = hints.revealed.entry(kind).or_insert(0);
+
+                if *revealed < code_text.chars().count() {
+                    // the letter the player has the most of pays for the hint
+                    let payment = collected_chars
+                        .values_map
+                        .iter()
+                        .filter(|(_, count)| **count > 0)
+                        .max_by_key(|(c, count)| (**count, std::cmp::Reverse(**c)))
+                        .map(|(c, _)| *c);
+                    match payment {
+                        Some(c) => {
+                            spend_letter(&mut collected_chars, c);
+                            *revealed += 1;
+                            print_to_console
+                                .send(PrintToConsoleEvent(format!("Traded a <{}> for a hint.", c)));
+                        }
+                        None => {
+                            print_to_console.send(PrintToConsoleEvent(
+                                "A hint costs one letter, collect some first.".to_string(),
+                            ));
+                            continue;
+                        }
+                    }
+                }
+
+                print_to_console.send(PrintToConsoleEvent(format!(
+                    "Next cheat code [{:?}]: {}",
+                    kind,
+                    hints.masked(&kind, &code_text)
+                )));
+            }
+            _ => {}
+        }
+    }
+}
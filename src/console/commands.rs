@@ -1,121 +1,263 @@
-use crate::audio::{GameAudioOptions, GameAudioState};
-use crate::cheat_codes::CheatCodeActivationResult;
-use crate::runner::CollectedChars;
-use crate::{cheat_codes::CheatCodeResource, states::GameStates};
+use crate::states::GameStates;
 
-use super::{event::*, CheatCodeActivatedEvent, ConsoleData};
+use super::args::{parse_args, split_commands, tokenize, ArgKind};
+use super::registry::{ConsoleCommand, ConsoleCommandEvent, ConsoleCommands};
+use super::script::ConsoleScripts;
+use super::{event::*, ConsoleData};
 use bevy::prelude::*;
 
+/// Commands handled by the console itself
+pub fn builtin_commands() -> Vec<ConsoleCommand> {
+    vec![
+        ConsoleCommand::new("help", "show the available commands"),
+        ConsoleCommand::new("cheat", "enable a cheat code to activate an ability")
+            .with_arg("code", ArgKind::Text)
+            .with_completer(super::registry::complete_cheat_codes),
+        ConsoleCommand::new("hint", "trade a letter for a letter of the next cheat code"),
+        ConsoleCommand::new("run", "run the commands of a script")
+            .with_arg("script", ArgKind::Text)
+            .with_completer(super::script::complete_scripts),
+        ConsoleCommand::new(
+            "set",
+            "define a variable, used as $name in the next commands",
+        )
+        .with_arg("name", ArgKind::Text)
+        .with_arg("value", ArgKind::Rest),
+        ConsoleCommand::new("echo", "print a message").with_optional_arg("text", ArgKind::Rest),
+        ConsoleCommand::new("clear", "clear the entire display"),
+        ConsoleCommand::new("exit", "exit this terminal to go back to boring reality"),
+    ]
+}
+
+/// Queue the commands of the entered line, separated by `;`
 pub fn command_handler(
     mut cmd_reader: EventReader<SendCommandEvent>,
     mut print_to_console: EventWriter<PrintToConsoleEvent>,
-    mut data: ResMut<ConsoleData>,
-    mut game_state: ResMut<State<GameStates>>,
-    mut cheat_codes_res: ResMut<CheatCodeResource>,
-    mut collected_chars: ResMut<CollectedChars>,
-    mut ev_writer: EventWriter<CheatCodeActivatedEvent>,
-    mut game_audio_state: ResMut<GameAudioState>,
+    mut scripts: ResMut<ConsoleScripts>,
 ) {
     for SendCommandEvent(command) in cmd_reader.iter() {
         // skip if the command is empty
-        if command.is_empty() {
-            return;
+        if command.trim().is_empty() {
+            continue;
         }
 
-        // extracting args
-        let args: Vec<&str> = command.trim().split(' ').collect();
-
         // show the command entered by the user if it's not a clear
-        if args[0] != "clear" {
+        if command.trim() != "clear" {
             let mut user_input = String::from("> ");
-            user_input.push_str(command.clone().trim());
+            user_input.push_str(command.trim());
             print_to_console.send(PrintToConsoleEvent(user_input));
         }
 
-        // dispatch the command
-        match args[0] {
-            "clear" => data.lines.clear(),
-            "help" => print_to_console.send(PrintToConsoleEvent(super::utils::display_help())),
-            "cheat" => {
-                print_to_console.send(PrintToConsoleEvent(format!(
-                    "Activating cheat code: <{}>...",
-                    args[1]
-                )));
-
-                let can_activate = is_valid_cheat(&mut collected_chars, args[1], &cheat_codes_res);
-
-                if can_activate {
-                    let activation_res = cheat_codes_res.activate_code(args[1]);
-                    print_to_console.send(PrintToConsoleEvent(format!(
-                        "Activation result: {}",
-                        activation_res.repr()
-                    )));
-
-                    if let CheatCodeActivationResult::Activated(kind) = activation_res {
-                        ev_writer.send(CheatCodeActivatedEvent(kind));
-                        game_audio_state.queue_sound(
-                            "powerup-sound".to_owned(),
-                            GameAudioOptions {
-                                ..Default::default()
-                            },
-                        );
-                    }
-                } else {
-                    print_to_console.send(PrintToConsoleEvent(format!(
-                        "Failed to activate. Need more information."
-                    )));
-                }
+        scripts.scripts_started = 0;
+        match split_commands(command) {
+            Ok(commands) => scripts.queue.extend(commands),
+            Err(err) => print_to_console.send(PrintToConsoleEvent(format!("Error: {}", err))),
+        }
+    }
+}
+
+/// Parse the next queued command and dispatch it to the plugin registering it,
+/// mistakes are printed with the usage of the command
+pub fn dispatch_command(
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    mut command_writer: EventWriter<ConsoleCommandEvent>,
+    mut scripts: ResMut<ConsoleScripts>,
+    commands: Res<ConsoleCommands>,
+) {
+    let command = match scripts.queue.pop_front() {
+        Some(command) => command,
+        None => return,
+    };
+
+    let command = match scripts.expand_variables(&command) {
+        Ok(command) => command,
+        Err(err) => {
+            print_to_console.send(PrintToConsoleEvent(format!("Error: {}", err)));
+            return;
+        }
+    };
+
+    // extracting args
+    let tokens = match tokenize(&command) {
+        Ok(tokens) if !tokens.is_empty() => tokens,
+        Ok(_) => return,
+        Err(err) => {
+            print_to_console.send(PrintToConsoleEvent(format!("Error: {}", err)));
+            return;
+        }
+    };
+
+    let console_command = match commands.get(&tokens[0]) {
+        Some(console_command) => console_command,
+        None => {
+            print_to_console.send(PrintToConsoleEvent(format!(
+                "Command \"{}\" not found.\nType \"help\" to print the list of available commands.",
+                tokens[0]
+            )));
+            return;
+        }
+    };
+
+    match parse_args(&console_command.args, &tokens[1..]) {
+        Ok(args) => command_writer.send(ConsoleCommandEvent {
+            name: console_command.name.to_string(),
+            args,
+        }),
+        Err(err) => print_to_console.send(PrintToConsoleEvent(format!(
+            "Error: {}\nUsage: {}",
+            err,
+            console_command.usage()
+        ))),
+    }
+}
+
+pub fn run_builtin_commands(
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    mut data: ResMut<ConsoleData>,
+    mut game_state: ResMut<State<GameStates>>,
+    commands: Res<ConsoleCommands>,
+) {
+    for event in command_reader.iter() {
+        match event.name.as_str() {
+            "clear" => data.clear_lines(),
+            "help" => {
+                print_to_console.send(PrintToConsoleEvent(super::utils::display_help(&commands)))
             }
             "exit" => {
                 print_to_console.send(PrintToConsoleEvent("Closing session...".to_string()));
-                game_state.pop().unwrap();
-            }
-            "log" => print_to_console.send(PrintToConsoleEvent(super::utils::display_random_log())),
-            _ => {
-                print_to_console.send(PrintToConsoleEvent(format!(
-                    "Command \"{}\" not found.\nType \"help\" to print the list of available commands.",
-                    args[0]
-                )));
+                // another transition may already be queued this frame
+                if let Err(err) = game_state.pop() {
+                    warn!("[ConsolePlugin] Unable to close the console: {:?}", err);
+                }
             }
+            _ => {}
         }
     }
 }
 
-pub fn is_valid_cheat(
-    collected_chars: &mut CollectedChars,
-    code_text: &str,
-    cheat_codes: &CheatCodeResource,
-) -> bool {
-    let original_collected_chars = collected_chars.values.clone();
-    let original_collected_chars_map = collected_chars.values_map.clone();
-
-    let cheats = cheat_codes.codes.clone();
-    let code = cheats
-        .into_iter()
-        .find(|(_kind, code)| code.text == code_text);
-
-    if let Some((_, cheat)) = code {
-        for ch in cheat.text.chars() {
-            let index_opt = collected_chars.values.iter().position(|val| *val == ch);
-
-            if let Some(index) = index_opt {
-                collected_chars.values.remove(index);
-
-                // Update values map
-                let char_entry = collected_chars.values_map.get(&ch);
-                if let Some(_count) = char_entry {
-                    *collected_chars.values_map.get_mut(&ch).unwrap() -= 1;
-                }
-            } else {
-                collected_chars.values = original_collected_chars;
-                collected_chars.values_map = original_collected_chars_map;
-                return false;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::event::Events;
+
+    struct Output(Vec<String>);
+
+    fn collect_output(mut reader: EventReader<PrintToConsoleEvent>, mut output: ResMut<Output>) {
+        output
+            .0
+            .extend(reader.iter().map(|PrintToConsoleEvent(line)| line.clone()));
+    }
+
+    /// The console systems without any window, the console being open over a run
+    fn console_app() -> App {
+        let mut commands = ConsoleCommands::new();
+        for command in builtin_commands() {
+            commands.add(command);
         }
-        return true;
+
+        let mut app = App::new();
+        app.insert_resource(commands)
+            .insert_resource(ConsoleScripts::new())
+            .insert_resource(ConsoleData {
+                input: String::new(),
+                cursor: 0,
+                selection_anchor: None,
+                clipboard: String::new(),
+                history_index: 0,
+                history: Vec::new(),
+                lines: Vec::new(),
+                scroll: 0,
+                lines_dirty: true,
+            })
+            .insert_resource(Output(Vec::new()))
+            .add_event::<SendCommandEvent>()
+            .add_event::<PrintToConsoleEvent>()
+            .add_event::<ConsoleCommandEvent>()
+            .add_state(GameStates::Main)
+            .add_system(command_handler.label("queue_commands"))
+            .add_system(
+                dispatch_command
+                    .label("dispatch_commands")
+                    .after("queue_commands"),
+            )
+            .add_system(
+                run_builtin_commands
+                    .label("run_commands")
+                    .after("dispatch_commands"),
+            )
+            .add_system(collect_output.after("run_commands"));
+
+        app.update();
+        app.world
+            .get_resource_mut::<State<GameStates>>()
+            .unwrap()
+            .push(GameStates::Console)
+            .unwrap();
+        app.update();
+        app
     }
 
-    collected_chars.values = original_collected_chars;
-    collected_chars.values_map = original_collected_chars_map;
-    false
+    /// Type a line in the console, returns what was printed
+    fn send(app: &mut App, line: &str) -> Vec<String> {
+        app.world
+            .get_resource_mut::<Events<SendCommandEvent>>()
+            .unwrap()
+            .send(SendCommandEvent(line.to_string()));
+        for _ in 0..3 {
+            app.update();
+        }
+        std::mem::take(&mut app.world.get_resource_mut::<Output>().unwrap().0)
+    }
+
+    fn current_state(app: &App) -> GameStates {
+        app.world
+            .get_resource::<State<GameStates>>()
+            .unwrap()
+            .current()
+            .clone()
+    }
+
+    #[test]
+    fn empty_line_prints_nothing() {
+        let mut app = console_app();
+        assert!(send(&mut app, "").is_empty());
+        assert!(send(&mut app, "   ").is_empty());
+        assert_eq!(current_state(&app), GameStates::Console);
+    }
+
+    #[test]
+    fn cheat_without_code_prints_usage() {
+        let mut app = console_app();
+        let output = send(&mut app, "cheat");
+        assert_eq!(output[0], "> cheat");
+        assert!(output[1].starts_with("Error: missing argument <code>\nUsage: cheat"));
+        assert_eq!(current_state(&app), GameStates::Console);
+    }
+
+    #[test]
+    fn invalid_lines_print_errors() {
+        let mut app = console_app();
+        let output = send(&mut app, "cheat \"jump");
+        assert_eq!(output[1], "Error: missing closing quote");
+
+        let output = send(&mut app, "clear now");
+        assert!(output[1].starts_with("Error: unexpected argument \"now\""));
+
+        let output = send(&mut app, "jump");
+        assert!(output[1].starts_with("Command \"jump\" not found."));
+        assert_eq!(current_state(&app), GameStates::Console);
+    }
+
+    #[test]
+    fn exit_closes_the_console() {
+        let mut app = console_app();
+        let output = send(&mut app, "exit");
+        assert_eq!(output, vec!["> exit", "Closing session..."]);
+        assert_eq!(current_state(&app), GameStates::Main);
+
+        // nothing left to pop, the error is only logged
+        send(&mut app, "exit");
+        assert_eq!(current_state(&app), GameStates::Main);
+    }
 }
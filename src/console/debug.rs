@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::{
+    args::ArgKind,
+    event::PrintToConsoleEvent,
+    registry::{AddConsoleCommand, CompletionContext, ConsoleCommand, ConsoleCommandEvent},
+    CheatCodeActivatedEvent,
+};
+use crate::cheat_codes::{CheatCodeKind, CheatCodeResource};
+use crate::enemies::{spawn_enemy, EnemyKind};
+use crate::runner::{CollectedChars, Player, LETTERS};
+
+/// Debug commands, only built with the `dev-console` feature
+pub struct DebugCommandsPlugin;
+
+impl Plugin for DebugCommandsPlugin {
+    fn build(&self, app: &mut App) {
+        info!("[ConsolePlugin] Developer commands enabled");
+
+        app.insert_resource(TimeScale(1.0));
+        app.add_console_command(
+            ConsoleCommand::new("give", "[dev] add letters to the inventory")
+                .with_arg("letters", ArgKind::Text),
+        )
+        .add_console_command(
+            ConsoleCommand::new("grant", "[dev] activate a cheat code without letters")
+                .with_arg("kind", ArgKind::Text)
+                .with_completer(complete_code_kinds),
+        )
+        .add_console_command(
+            ConsoleCommand::new(
+                "revoke",
+                "[dev] deactivate a cheat code, all of them by default",
+            )
+            .with_optional_arg("kind", ArgKind::Text)
+            .with_completer(complete_code_kinds),
+        )
+        .add_console_command(
+            ConsoleCommand::new("teleport", "[dev] move the player, in pixels")
+                .with_arg("x", ArgKind::Number)
+                .with_optional_arg("y", ArgKind::Number),
+        )
+        .add_console_command(
+            ConsoleCommand::new("spawn", "[dev] spawn an enemy, in pixels")
+                .with_arg("enemy", ArgKind::Text)
+                .with_arg("x", ArgKind::Number)
+                .with_arg("y", ArgKind::Number),
+        )
+        .add_console_command(ConsoleCommand::new("god", "[dev] toggle invulnerability"))
+        .add_console_command(
+            ConsoleCommand::new("timescale", "[dev] speed of the physics simulation")
+                .with_arg("scale", ArgKind::Number),
+        );
+
+        app.add_system(run_debug_commands);
+        app.add_system(apply_time_scale);
+    }
+}
+
+/// Speed of the physics simulation, 1 being real time
+pub struct TimeScale(pub f32);
+
+fn complete_code_kinds(context: &CompletionContext) -> Vec<String> {
+    context
+        .cheat_codes
+        .codes
+        .keys()
+        .map(|kind| format!("{:?}", kind))
+        .collect()
+}
+
+fn find_code_kind(cheat_codes: &CheatCodeResource, name: &str) -> Option<CheatCodeKind> {
+    cheat_codes
+        .codes
+        .keys()
+        .find(|kind| format!("{:?}", kind).eq_ignore_ascii_case(name))
+        .copied()
+}
+
+fn run_debug_commands(
+    mut commands: Commands,
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    mut cheat_codes: ResMut<CheatCodeResource>,
+    mut activated_writer: EventWriter<CheatCodeActivatedEvent>,
+    mut collected_chars: ResMut<CollectedChars>,
+    mut player_query: Query<(&mut Player, &mut RigidBodyPositionComponent)>,
+    mut time_scale: ResMut<TimeScale>,
+    rapier_config: Res<RapierConfiguration>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in command_reader.iter() {
+        let message = match event.name.as_str() {
+            "give" => {
+                let letters: Vec<char> = event
+                    .text(0)
+                    .unwrap_or_default()
+                    .to_lowercase()
+                    .chars()
+                    .filter(|c| LETTERS.contains(c))
+                    .collect();
+                for c in letters.iter() {
+                    collected_chars.values.push(*c);
+                    *collected_chars.values_map.entry(*c).or_insert(0) += 1;
+                }
+                format!("Added {} letters.", letters.len())
+            }
+            "grant" => {
+                let name = event.text(0).unwrap_or_default();
+                match find_code_kind(&cheat_codes, name) {
+                    Some(kind) if cheat_codes.is_code_activated(&kind) => {
+                        format!("[{:?}] already activated", kind)
+                    }
+                    Some(kind) => {
+                        let text = cheat_codes.codes[&kind].text.clone();
+                        cheat_codes.activate_code(&text);
+                        activated_writer.send(CheatCodeActivatedEvent(kind));
+                        format!("[{:?}] granted", kind)
+                    }
+                    None => format!("Unknown cheat code kind \"{}\"", name),
+                }
+            }
+            "revoke" => match event.text(0) {
+                Some(name) => match find_code_kind(&cheat_codes, name) {
+                    Some(kind) => {
+                        cheat_codes.deactivate_code(&kind);
+                        format!("[{:?}] revoked", kind)
+                    }
+                    None => format!("Unknown cheat code kind \"{}\"", name),
+                },
+                None => {
+                    let activated = cheat_codes.activated().to_vec();
+                    for kind in activated.iter() {
+                        cheat_codes.deactivate_code(kind);
+                    }
+                    format!("Revoked {} cheat codes", activated.len())
+                }
+            },
+            "teleport" => match player_query.get_single_mut() {
+                Ok((_, mut rb_pos)) => {
+                    let x = event.number(0).unwrap_or_default();
+                    // high enough to land on the platforms by default
+                    let y = event.number(1).unwrap_or(200.0);
+                    let position =
+                        Isometry::translation(x / rapier_config.scale, y / rapier_config.scale);
+                    rb_pos.position = position;
+                    rb_pos.next_position = position;
+                    format!("Teleported to ({}, {})", x, y)
+                }
+                Err(_) => "No player to teleport".to_string(),
+            },
+            "spawn" => {
+                let name = event.text(0).unwrap_or_default();
+                let position = Vec2::new(
+                    event.number(1).unwrap_or_default(),
+                    event.number(2).unwrap_or_default(),
+                );
+                match EnemyKind::from_name(name) {
+                    Some(kind) => {
+                        spawn_enemy(
+                            &kind,
+                            position,
+                            &mut commands,
+                            &rapier_config,
+                            &asset_server,
+                        );
+                        format!("Spawned {:?} at ({}, {})", kind, position.x, position.y)
+                    }
+                    None => format!("Unknown enemy \"{}\"", name),
+                }
+            }
+            "god" => match player_query.get_single_mut() {
+                Ok((mut player, _)) => {
+                    player.god_mode = !player.god_mode;
+                    format!("God mode {}", if player.god_mode { "on" } else { "off" })
+                }
+                Err(_) => "No player".to_string(),
+            },
+            "timescale" => {
+                let scale = event.number(0).unwrap_or(1.0);
+                if scale > 0.0 && scale <= 10.0 {
+                    time_scale.0 = scale;
+                    format!("Time scale set to {}", scale)
+                } else {
+                    "The time scale must be above 0 and at most 10".to_string()
+                }
+            }
+            _ => continue,
+        };
+        print_to_console.send(PrintToConsoleEvent(message));
+    }
+}
+
+/// Step the physics with a scaled fixed timestep while the time scale isn't 1
+fn apply_time_scale(
+    time: Res<Time>,
+    time_scale: Res<TimeScale>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut integration_parameters: ResMut<IntegrationParameters>,
+) {
+    if (time_scale.0 - 1.0).abs() < f32::EPSILON {
+        if time_scale.is_changed() {
+            rapier_config.timestep_mode = TimestepMode::VariableTimestep;
+        }
+        return;
+    }
+
+    rapier_config.timestep_mode = TimestepMode::FixedTimestep;
+    integration_parameters.dt = time.delta_seconds() * time_scale.0;
+}
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 
-use crate::{cheat_codes::CheatCodeResource, toast::ShowToast};
+use crate::{cheat_codes::CheatCodeResource, controls::ActionInput, toast::ShowToast};
 
 use super::{CheatCodeActivatedEvent, ConsoleData};
 
@@ -14,7 +14,7 @@ pub fn add_message_events_to_console(
     mut ev_console_message: EventReader<PrintToConsoleEvent>,
 ) {
     for PrintToConsoleEvent(message) in ev_console_message.iter() {
-        data.lines.push(message.clone());
+        data.push_line(message);
     }
 }
 
@@ -22,11 +22,12 @@ pub fn show_help_text(
     mut ev_reader: EventReader<CheatCodeActivatedEvent>,
     mut ev_writer: EventWriter<ShowToast>,
     cheat_code_res: Res<CheatCodeResource>,
+    actions: ActionInput,
 ) {
     for CheatCodeActivatedEvent(kind) in ev_reader.iter() {
         let code = cheat_code_res.codes.get(kind).unwrap();
         ev_writer.send(ShowToast {
-            value: code.help_text.clone(),
+            value: actions.fill_prompts(&code.help_text),
             duration: Duration::from_secs(5),
         })
     }
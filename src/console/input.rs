@@ -1,126 +1,293 @@
-use bevy::{input::keyboard::KeyboardInput, prelude::*};
+use bevy::{input::keyboard::KeyboardInput, prelude::*, window::ReceivedCharacter};
 
-use super::event::SendCommandEvent;
+use super::{
+    event::{PrintToConsoleEvent, SendCommandEvent},
+    registry::{common_prefix, CompletionContext, ConsoleCommands},
+    ConsoleData,
+};
+use crate::cheat_codes::CheatCodeResource;
+use crate::runner::CollectedChars;
+
+// longest command that can be typed, in characters
+pub const MAX_INPUT_LENGTH: usize = 144;
 
 pub fn handle_input_keys(
-    mut data: ResMut<super::ConsoleData>,
+    mut data: ResMut<ConsoleData>,
+    mut evr_chars: EventReader<ReceivedCharacter>,
     mut evr_keys: EventReader<KeyboardInput>,
     mut send_command: EventWriter<SendCommandEvent>,
     keyboard_input: Res<Input<KeyCode>>,
 ) {
-    // don't do anything if control key is pressed
-    if keyboard_input.pressed(KeyCode::LControl) || keyboard_input.pressed(KeyCode::RControl) {
-        return;
+    let ctrl =
+        keyboard_input.pressed(KeyCode::LControl) || keyboard_input.pressed(KeyCode::RControl);
+    let shift = keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift);
+
+    // typed text, already translated by the keyboard layout
+    for ev in evr_chars.iter() {
+        if !ctrl && !ev.char.is_control() {
+            data.insert_text(&ev.char.to_string());
+        }
     }
 
+    // editing keys, key repeat included
     for ev in evr_keys.iter() {
-        if ev.state.is_pressed() {
-            if let Some(key_code) = ev.key_code {
-                match key_code {
-                    KeyCode::Back => {
-                        if !data.input.is_empty() {
-                            data.input.pop();
-                        }
-                    }
-                    KeyCode::Space => data.input.push(' '),
-                    KeyCode::Tab => data.input.push_str("  "),
-                    KeyCode::Comma => data.input.push(','),
-                    KeyCode::Colon => data.input.push(':'),
-                    KeyCode::Semicolon => data.input.push(';'),
-                    KeyCode::Apostrophe => data.input.push('\''),
-                    KeyCode::At => data.input.push('@'),
-                    KeyCode::LBracket => data.input.push('['),
-                    KeyCode::RBracket => data.input.push(']'),
-                    KeyCode::Minus | KeyCode::NumpadSubtract => data.input.push('-'),
-                    KeyCode::Period | KeyCode::NumpadDecimal => data.input.push('.'),
-                    KeyCode::Asterisk | KeyCode::NumpadMultiply => data.input.push('*'),
-                    KeyCode::Slash | KeyCode::NumpadDivide => data.input.push('/'),
-                    KeyCode::Plus | KeyCode::NumpadAdd => data.input.push('+'),
-                    KeyCode::Key0 | KeyCode::Numpad0 => data.input.push('0'),
-                    KeyCode::Key1 | KeyCode::Numpad1 => data.input.push('1'),
-                    KeyCode::Key2 | KeyCode::Numpad2 => data.input.push('2'),
-                    KeyCode::Key3 | KeyCode::Numpad3 => data.input.push('3'),
-                    KeyCode::Key4 | KeyCode::Numpad4 => data.input.push('4'),
-                    KeyCode::Key5 | KeyCode::Numpad5 => data.input.push('5'),
-                    KeyCode::Key6 | KeyCode::Numpad6 => data.input.push('6'),
-                    KeyCode::Key7 | KeyCode::Numpad7 => data.input.push('7'),
-                    KeyCode::Key8 | KeyCode::Numpad8 => data.input.push('8'),
-                    KeyCode::Key9 | KeyCode::Numpad9 => data.input.push('9'),
-
-                    KeyCode::LShift
-                    | KeyCode::RShift
-                    | KeyCode::Escape
-                    | KeyCode::LAlt
-                    | KeyCode::RAlt
-                    | KeyCode::LControl
-                    | KeyCode::RControl
-                    | KeyCode::Right
-                    | KeyCode::Left
-                    | KeyCode::F1
-                    | KeyCode::F2
-                    | KeyCode::F3
-                    | KeyCode::F4
-                    | KeyCode::F5
-                    | KeyCode::F6
-                    | KeyCode::F7
-                    | KeyCode::F8
-                    | KeyCode::F9
-                    | KeyCode::F10
-                    | KeyCode::F11
-                    | KeyCode::F12
-                    | KeyCode::Insert
-                    | KeyCode::Delete
-                    | KeyCode::Grave
-                    | KeyCode::Backslash => {}
-
-                    KeyCode::Up => {
-                        // don't do anything if at top
-                        if data.history_index == 0 {
-                            return;
-                        }
-
-                        data.history_index = (data.history_index - 1).max(0);
-                        data.input = data.history[data.history_index].clone();
-                    }
-
-                    KeyCode::Down => {
-                        // don't do anything if at the bottom
-                        if data.history_index == data.history.len() {
-                            return;
-                        }
-
-                        data.history_index = (data.history_index + 1).min(data.history.len());
-
-                        if data.history_index != data.history.len() {
-                            data.input = data.history[data.history_index].clone();
-                        } else {
-                            data.input.clear();
-                        }
-                    }
-
-                    KeyCode::Return | KeyCode::NumpadEnter => {
-                        // sending the command
-                        let command = data.input.clone();
-                        send_command.send(SendCommandEvent(command.clone()));
-                        data.history.push(command.clone());
-                        data.history_index = data.history.len();
-                        // clearing the input
-                        data.input.clear();
-                    }
-                    _ => {
-                        let key_code_str = if keyboard_input.pressed(KeyCode::LShift)
-                            || keyboard_input.pressed(KeyCode::RShift)
-                        {
-                            format!("{:?}", key_code).to_uppercase()
-                        } else {
-                            format!("{:?}", key_code).to_lowercase()
-                        };
-
-                        // trace!("Pressed key: {:?}", key_code_str);
-                        data.input.push_str(&key_code_str);
-                    }
+        if !ev.state.is_pressed() {
+            continue;
+        }
+        let key_code = match ev.key_code {
+            Some(key_code) => key_code,
+            None => continue,
+        };
+
+        match key_code {
+            KeyCode::Back => data.delete_backward(ctrl),
+            KeyCode::Delete => data.delete_forward(ctrl),
+            KeyCode::Left => {
+                let to = if ctrl {
+                    data.previous_word_start()
+                } else if !shift && data.selection_range().is_some() {
+                    data.selection_range().unwrap().0
+                } else {
+                    data.cursor.saturating_sub(1)
+                };
+                data.move_cursor(to, shift);
+            }
+            KeyCode::Right => {
+                let to = if ctrl {
+                    data.next_word_end()
+                } else if !shift && data.selection_range().is_some() {
+                    data.selection_range().unwrap().1
+                } else {
+                    data.cursor + 1
+                };
+                data.move_cursor(to, shift);
+            }
+            KeyCode::Home => data.move_cursor(0, shift),
+            KeyCode::End => {
+                let end = data.input_length();
+                data.move_cursor(end, shift);
+            }
+            KeyCode::A if ctrl => {
+                let end = data.input_length();
+                data.move_cursor(0, false);
+                data.move_cursor(end, true);
+            }
+            KeyCode::C if ctrl => {
+                if let Some(text) = data.selected_text() {
+                    data.clipboard = text;
+                }
+            }
+            KeyCode::X if ctrl => {
+                if let Some(text) = data.selected_text() {
+                    data.clipboard = text;
+                    data.delete_backward(false);
+                }
+            }
+            KeyCode::V if ctrl => {
+                let text = data.clipboard.clone();
+                data.insert_text(&text);
+            }
+
+            KeyCode::Up => {
+                // don't do anything if at top
+                if data.history_index == 0 {
+                    continue;
+                }
+
+                data.history_index -= 1;
+                let command = data.history[data.history_index].clone();
+                data.set_input(command);
+            }
+
+            KeyCode::Down => {
+                // don't do anything if at the bottom
+                if data.history_index == data.history.len() {
+                    continue;
                 }
+
+                data.history_index += 1;
+
+                if data.history_index != data.history.len() {
+                    let command = data.history[data.history_index].clone();
+                    data.set_input(command);
+                } else {
+                    data.set_input(String::new());
+                }
+            }
+
+            KeyCode::Return | KeyCode::NumpadEnter => {
+                submit_input(&mut data, &mut send_command);
             }
+            _ => {}
+        }
+    }
+}
+
+/// Tab completes the word before the caret, listing the candidates when there are several
+pub fn complete_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut data: ResMut<ConsoleData>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    commands: Res<ConsoleCommands>,
+    cheat_codes: Res<CheatCodeResource>,
+    collected_chars: Res<CollectedChars>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Tab) {
+        return;
+    }
+
+    let before_caret: String = data.input.chars().take(data.cursor).collect();
+    let context = CompletionContext {
+        cheat_codes: &cheat_codes,
+        collected_chars: &collected_chars,
+    };
+    let candidates = commands.complete(&before_caret, &context);
+    let typed = before_caret.split(' ').last().unwrap_or("").to_string();
+
+    let completion = match candidates.len() {
+        0 => return,
+        1 => format!("{} ", candidates[0]),
+        _ => {
+            let prefix = common_prefix(&candidates);
+            if prefix.len() == typed.len() {
+                print_to_console.send(PrintToConsoleEvent(candidates.join("  ")));
+            }
+            prefix
+        }
+    };
+
+    data.selection_anchor = None;
+    data.insert_text(&completion[typed.len()..]);
+}
+
+/// Send the typed command and add it to the history
+pub fn submit_input(data: &mut ConsoleData, send_command: &mut EventWriter<SendCommandEvent>) {
+    let command = data.input.clone();
+    send_command.send(SendCommandEvent(command.clone()));
+    data.history.push(command);
+    data.history_index = data.history.len();
+    // clearing the input
+    data.set_input(String::new());
+}
+
+// positions are counted in characters, not bytes
+fn byte_index(text: &str, position: usize) -> usize {
+    text.char_indices()
+        .nth(position)
+        .map(|(index, _)| index)
+        .unwrap_or(text.len())
+}
+
+impl ConsoleData {
+    pub fn input_length(&self) -> usize {
+        self.input.chars().count()
+    }
+
+    /// Replace the whole input, the caret goes at the end
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+        self.cursor = self.input_length();
+        self.selection_anchor = None;
+    }
+
+    /// Selected characters as a start..end range, if any
+    pub fn selection_range(&self) -> Option<(usize, usize)> {
+        match self.selection_anchor {
+            Some(anchor) if anchor != self.cursor => {
+                Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+            }
+            _ => None,
+        }
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        self.selection_range()
+            .map(|(start, end)| self.input.chars().skip(start).take(end - start).collect())
+    }
+
+    /// Move the caret, extending the selection from where it was when `select` is set
+    pub fn move_cursor(&mut self, to: usize, select: bool) {
+        if select {
+            if self.selection_anchor.is_none() {
+                self.selection_anchor = Some(self.cursor);
+            }
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor = to.min(self.input_length());
+    }
+
+    fn delete_range(&mut self, start: usize, end: usize) {
+        let start_byte = byte_index(&self.input, start);
+        let end_byte = byte_index(&self.input, end);
+        self.input.replace_range(start_byte..end_byte, "");
+        self.cursor = start;
+        self.selection_anchor = None;
+    }
+
+    /// Insert at the caret, replacing the selection
+    pub fn insert_text(&mut self, text: &str) {
+        if let Some((start, end)) = self.selection_range() {
+            self.delete_range(start, end);
+        }
+        self.selection_anchor = None;
+
+        let room = MAX_INPUT_LENGTH.saturating_sub(self.input_length());
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(room)
+            .collect();
+        let index = byte_index(&self.input, self.cursor);
+        self.input.insert_str(index, &text);
+        self.cursor += text.chars().count();
+    }
+
+    /// Backspace, a whole word with `word` set
+    pub fn delete_backward(&mut self, word: bool) {
+        if let Some((start, end)) = self.selection_range() {
+            self.delete_range(start, end);
+        } else if word {
+            let start = self.previous_word_start();
+            self.delete_range(start, self.cursor);
+        } else if self.cursor > 0 {
+            self.delete_range(self.cursor - 1, self.cursor);
+        }
+    }
+
+    /// Delete, a whole word with `word` set
+    pub fn delete_forward(&mut self, word: bool) {
+        if let Some((start, end)) = self.selection_range() {
+            self.delete_range(start, end);
+        } else if word {
+            let end = self.next_word_end();
+            self.delete_range(self.cursor, end);
+        } else if self.cursor < self.input_length() {
+            self.delete_range(self.cursor, self.cursor + 1);
+        }
+    }
+
+    fn previous_word_start(&self) -> usize {
+        let chars: Vec<char> = self.input.chars().collect();
+        let mut position = self.cursor;
+        while position > 0 && !chars[position - 1].is_alphanumeric() {
+            position -= 1;
+        }
+        while position > 0 && chars[position - 1].is_alphanumeric() {
+            position -= 1;
+        }
+        position
+    }
+
+    fn next_word_end(&self) -> usize {
+        let chars: Vec<char> = self.input.chars().collect();
+        let mut position = self.cursor;
+        while position < chars.len() && !chars[position].is_alphanumeric() {
+            position += 1;
+        }
+        while position < chars.len() && chars[position].is_alphanumeric() {
+            position += 1;
         }
+        position
     }
 }
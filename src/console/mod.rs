@@ -4,8 +4,11 @@ use bevy_loading::prelude::*;
 use self::{
     event::{PrintToConsoleEvent, SendCommandEvent},
     loading_screen::LoadingScreenPlugin,
+    registry::ConsoleCommands,
 };
 use crate::audio::{GameAudioOptions, GameAudioState};
+use crate::controls::{Action, ActionInput};
+use crate::run::NewRunStage;
 use crate::runner::Player;
 use crate::states::GameStates;
 use crate::{
@@ -13,13 +16,26 @@ use crate::{
     interactables::{InteractableComponent, InteractableType},
 };
 
+mod args;
+mod cheat;
 mod commands;
+#[cfg(feature = "dev-console")]
+mod debug;
 mod event;
 mod input;
 mod loading_screen;
+mod picker;
+mod registry;
+mod script;
+mod scrollback;
 mod ui;
 mod utils;
 
+pub use self::args::ArgKind;
+pub use self::cheat::CheatHints;
+pub use self::event::PrintToConsoleEvent;
+pub use self::registry::{AddConsoleCommand, ConsoleCommand, ConsoleCommandEvent};
+
 pub struct CheatCodeActivatedEvent(pub CheatCodeKind);
 
 #[derive(Component)]
@@ -46,16 +62,38 @@ impl Plugin for ConsolePlugin {
 
         // plugin building
         app.add_event::<CheatCodeActivatedEvent>();
+        app.add_event::<ConsoleCommandEvent>();
+        for command in commands::builtin_commands() {
+            app.add_console_command(command);
+        }
+        #[cfg(feature = "dev-console")]
+        app.add_plugin(debug::DebugCommandsPlugin);
         app.insert_resource(ConsoleData {
             input: String::from(""),
+            cursor: 0,
+            selection_anchor: None,
+            clipboard: String::new(),
             history_index: 0,
             history: Vec::new(),
-            lines: utils::welcome_lines(),
+            // filled with the welcome message when a run starts
+            lines: Vec::new(),
+            scroll: 0,
+            lines_dirty: true,
         })
+        .insert_resource(picker::CharPicker::new())
+        .insert_resource(cheat::CheatHints::new())
+        .insert_resource(script::ConsoleScripts::new())
         .add_event::<PrintToConsoleEvent>()
         .add_event::<SendCommandEvent>()
+        .add_system_to_stage(NewRunStage, reset_console_data)
+        .add_system_to_stage(NewRunStage, cheat::reset_cheat_hints)
+        .add_system_to_stage(NewRunStage, script::reset_console_scripts)
         // on enter
-        .add_system_set(SystemSet::on_enter(GameStates::Console).with_system(ui::build_ui))
+        .add_system_set(
+            SystemSet::on_enter(GameStates::Console)
+                .with_system(ui::build_ui)
+                .with_system(picker::reset_picker),
+        )
         // on update
         .add_system_set(
             SystemSet::on_update(GameStates::Console)
@@ -68,17 +106,35 @@ impl Plugin for ConsolePlugin {
                 .with_system(update_input_area)
                 .with_system(update_lines_area)
                 .with_system(event::add_message_events_to_console)
+                .with_system(picker::update_picker)
                 .label("update_ui"),
         )
         .add_system_set(
             SystemSet::on_update(GameStates::Console)
                 .with_system(input::handle_input_keys)
-                .with_system(commands::command_handler)
+                .with_system(input::complete_input)
+                .with_system(scrollback::scroll_lines)
+                .with_system(picker::handle_picker_input)
+                .with_system(commands::command_handler.label("queue_commands"))
+                .with_system(
+                    commands::dispatch_command
+                        .label("dispatch_commands")
+                        .after("queue_commands"),
+                )
                 .after("update_ui"),
         )
+        .add_system_set(
+            SystemSet::on_update(GameStates::Console)
+                .with_system(commands::run_builtin_commands)
+                .with_system(cheat::run_cheat_commands)
+                .with_system(script::run_script_commands)
+                .after("dispatch_commands"),
+        )
         // on exit
         .add_system_set(
-            SystemSet::on_exit(GameStates::Console).with_system(destroy_console_state_entities),
+            SystemSet::on_exit(GameStates::Console)
+                .with_system(destroy_console_state_entities)
+                .with_system(script::clear_script_queue),
         );
     }
 }
@@ -104,9 +160,28 @@ fn load_overlay(
 
 pub struct ConsoleData {
     input: String,
+    // caret position in characters
+    cursor: usize,
+    // other end of the selection, the caret being the moving end
+    selection_anchor: Option<usize>,
+    clipboard: String,
     history_index: usize,
     history: Vec<String>,
     lines: Vec<String>,
+    // rows scrolled up from the bottom of the scrollback
+    scroll: usize,
+    // the lines area is only rebuilt when the lines or the scroll change
+    lines_dirty: bool,
+}
+
+fn reset_console_data(mut data: ResMut<ConsoleData>, commands: Res<ConsoleCommands>) {
+    data.set_input(String::new());
+    data.history_index = 0;
+    data.history.clear();
+    data.clear_lines();
+    for line in utils::welcome_lines(&commands) {
+        data.push_line(&line);
+    }
 }
 
 fn destroy_console_state_entities(
@@ -120,24 +195,23 @@ fn destroy_console_state_entities(
     info!("[ConsolePlugin] Exiting state");
 }
 
-fn close_console_handler(
-    mut keyboard: ResMut<Input<KeyCode>>,
-    mut game_state: ResMut<State<GameStates>>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
-        game_state.pop().unwrap();
-        keyboard.reset(KeyCode::Escape);
+fn close_console_handler(mut actions: ActionInput, mut game_state: ResMut<State<GameStates>>) {
+    if actions.just_pressed(Action::Pause) {
+        // `exit` may have closed the console this frame already
+        if game_state.pop().is_ok() {
+            actions.reset(Action::Pause);
+        }
     }
 }
 
 fn open_console_handler(
-    mut keyboard: ResMut<Input<KeyCode>>,
+    mut actions: ActionInput,
     mut game_state: ResMut<State<GameStates>>,
     player_query: Query<&Transform, With<Player>>,
     interactable_query: Query<(&InteractableComponent, &Transform)>,
     mut game_audio_state: ResMut<GameAudioState>,
 ) {
-    if keyboard.just_released(KeyCode::E) {
+    if actions.just_released(Action::Interact) {
         // Only open the terminal when in range
         if let Some(player) = player_query.iter().next() {
             for (interactable, transform) in interactable_query.iter() {
@@ -153,7 +227,7 @@ fn open_console_handler(
                             && distance_y >= -range
                         {
                             game_state.push(GameStates::ConsoleLoading).unwrap();
-                            keyboard.reset(KeyCode::E);
+                            actions.reset(Action::Interact);
                             game_audio_state.queue_sound(
                                 "crt-sound".to_owned(),
                                 GameAudioOptions {
@@ -170,51 +244,108 @@ fn open_console_handler(
 }
 
 pub fn update_lines_area(
-    data: Res<ConsoleData>,
+    mut data: ResMut<ConsoleData>,
     console_assets: Res<ConsoleAssets>,
+    windows: Res<Windows>,
     mut lines_area_query: Query<&mut Text, With<ui::LinesArea>>,
 ) {
-    let sections_text = data.lines.join("\n");
-    let sections = vec![TextSection {
-        value: sections_text,
-        style: TextStyle {
-            font: console_assets.crt_font.clone(),
-            font_size: 16.,
-            color: Color::rgba_u8(76, 207, 76, 255),
-        },
+    let mut text = lines_area_query.single_mut();
+    // the text is empty when the UI was just built
+    if !data.lines_dirty && !text.sections.is_empty() {
+        return;
+    }
+    data.lines_dirty = false;
+
+    let (columns, rows) = scrollback::lines_area_size(windows.get_primary().unwrap());
+    let wrapped: Vec<String> = data
+        .lines
+        .iter()
+        .flat_map(|line| scrollback::wrap_line(line, columns))
+        .collect();
+
+    data.scroll = data.scroll.min(wrapped.len().saturating_sub(rows));
+    let end = wrapped.len() - data.scroll;
+    // the last row tells how far the bottom is when scrolled up
+    let shown_rows = if data.scroll > 0 {
+        rows.saturating_sub(1).max(1)
+    } else {
+        rows
+    };
+    let start = end.saturating_sub(shown_rows);
+
+    let style = |color: Color| TextStyle {
+        font: console_assets.crt_font.clone(),
+        font_size: scrollback::FONT_SIZE,
+        color,
+    };
+    let mut sections = vec![TextSection {
+        value: wrapped[start..end].join("\n"),
+        style: style(Color::rgba_u8(76, 207, 76, 255)),
     }];
+    if data.scroll > 0 {
+        sections.push(TextSection {
+            value: format!(
+                "\n-- {} more lines below, PageDown to scroll --",
+                data.scroll
+            ),
+            style: style(Color::rgba_u8(38, 120, 38, 255)),
+        });
+    }
 
-    let mut text = lines_area_query.single_mut();
     text.sections = sections;
 }
 
 pub fn update_input_area(
     mut command_input_query: Query<&mut Text, With<ui::CommandInput>>,
-    mut state: ResMut<ConsoleData>,
+    state: Res<ConsoleData>,
     console_assets: Res<ConsoleAssets>,
     time: Res<Time>,
 ) {
     let mut text = command_input_query.single_mut();
-    text.sections = vec![];
 
-    if state.input.len() > 144 {
-        let trimmed_command = state.input[..144].to_string();
-        state.input = trimmed_command;
-    }
+    let style = |color: Color| TextStyle {
+        font: console_assets.crt_font.clone(),
+        font_size: 16.,
+        color,
+    };
+    let input_color = Color::rgba_u8(102, 255, 102, 255);
 
-    let mut to_show = String::from("user@hacked_pc > ");
-    to_show.push_str(&state.input);
+    let chars: Vec<char> = state.input.chars().collect();
+    let (selection_start, selection_end) = state
+        .selection_range()
+        .unwrap_or((state.cursor, state.cursor));
+    let part = |from: usize, to: usize| -> String { chars[from..to].iter().collect() };
 
-    if (time.seconds_since_startup() * 3.0) as u64 % 2 == 0 {
-        to_show.push('_');
-    }
+    // the caret always takes a cell so the text doesn't move when it blinks
+    let caret_color = if (time.seconds_since_startup() * 3.0) as u64 % 2 == 0 {
+        input_color
+    } else {
+        Color::rgba_u8(0, 0, 0, 0)
+    };
+    let caret = TextSection {
+        value: "_".to_string(),
+        style: style(caret_color),
+    };
+    let selected = TextSection {
+        value: part(selection_start, selection_end),
+        style: style(Color::rgba_u8(220, 255, 220, 255)),
+    };
 
-    text.sections.push(TextSection {
-        value: to_show,
-        style: TextStyle {
-            font: console_assets.crt_font.clone(),
-            font_size: 16.,
-            color: Color::rgba_u8(102, 255, 102, 255),
-        },
+    let mut sections = vec![TextSection {
+        value: format!("user@hacked_pc > {}", part(0, selection_start)),
+        style: style(input_color),
+    }];
+    if state.cursor == selection_start {
+        sections.push(caret);
+        sections.push(selected);
+    } else {
+        sections.push(selected);
+        sections.push(caret);
+    }
+    sections.push(TextSection {
+        value: part(selection_end, chars.len()),
+        style: style(input_color),
     });
+
+    text.sections = sections;
 }
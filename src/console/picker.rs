@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+
+use super::{event::SendCommandEvent, input, ConsoleAssets, ConsoleData};
+use crate::controls::ActionInput;
+use crate::runner::LETTERS;
+
+// characters shown on each side of the selected one
+const PICKER_RADIUS: usize = 6;
+
+// Components
+#[derive(Component)]
+pub struct CharPickerText;
+
+/// On-screen keyboard letting the console be used with a gamepad
+pub struct CharPicker {
+    pub index: usize,
+}
+
+impl CharPicker {
+    pub fn new() -> Self {
+        Self { index: 0 }
+    }
+}
+
+// letters and digits of the cheat codes plus a space
+fn picker_chars() -> Vec<char> {
+    let mut chars = LETTERS.to_vec();
+    chars.push(' ');
+    chars
+}
+
+pub fn reset_picker(mut picker: ResMut<CharPicker>) {
+    picker.index = 0;
+}
+
+/// D-pad picks a character, A types it, X erases and Y sends the command
+pub fn handle_picker_input(
+    actions: ActionInput,
+    mut picker: ResMut<CharPicker>,
+    mut data: ResMut<ConsoleData>,
+    mut send_command: EventWriter<SendCommandEvent>,
+) {
+    let chars = picker_chars();
+
+    if actions.any_button_just_pressed(&[GamepadButtonType::DPadLeft]) {
+        picker.index = (picker.index + chars.len() - 1) % chars.len();
+    }
+    if actions.any_button_just_pressed(&[GamepadButtonType::DPadRight]) {
+        picker.index = (picker.index + 1) % chars.len();
+    }
+
+    if actions.any_button_just_pressed(&[GamepadButtonType::South]) {
+        data.insert_text(&chars[picker.index].to_string());
+    }
+    if actions.any_button_just_pressed(&[GamepadButtonType::West]) {
+        data.delete_backward(false);
+    }
+    if actions.any_button_just_pressed(&[GamepadButtonType::North]) {
+        input::submit_input(&mut data, &mut send_command);
+    }
+}
+
+/// The picker is only shown while a gamepad is connected
+pub fn update_picker(
+    actions: ActionInput,
+    picker: Res<CharPicker>,
+    console_assets: Res<ConsoleAssets>,
+    mut query: Query<(&mut Text, &mut Style), With<CharPickerText>>,
+) {
+    let (mut text, mut style) = match query.get_single_mut() {
+        Ok(picker_text) => picker_text,
+        Err(_) => return,
+    };
+
+    if !actions.has_gamepad() {
+        style.display = Display::None;
+        return;
+    }
+    style.display = Display::Flex;
+
+    if !picker.is_changed() && !text.sections.is_empty() {
+        return;
+    }
+
+    let chars = picker_chars();
+    let window = |from: usize, count: usize| -> String {
+        (0..count)
+            .map(|i| match chars[(from + i) % chars.len()] {
+                ' ' => "_ ".to_string(),
+                c => format!("{} ", c),
+            })
+            .collect()
+    };
+
+    let style_with = |color: Color| TextStyle {
+        font: console_assets.crt_font.clone(),
+        font_size: 16.,
+        color,
+    };
+    let normal = style_with(Color::rgba_u8(76, 207, 76, 255));
+    let selected = style_with(Color::rgba_u8(102, 255, 102, 255));
+
+    let first = picker.index + chars.len() - PICKER_RADIUS;
+    text.sections = vec![
+        TextSection {
+            value: window(first, PICKER_RADIUS),
+            style: normal.clone(),
+        },
+        TextSection {
+            value: format!("[{}]", window(picker.index, 1).trim_end()),
+            style: selected,
+        },
+        TextSection {
+            value: format!(
+                " {}   A type  X erase  Y send  Start close",
+                window(picker.index + 1, PICKER_RADIUS)
+            ),
+            style: normal,
+        },
+    ];
+}
@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::prelude::*;
+
+use super::args::{ArgKind, ArgSpec, ArgValue};
+use crate::cheat_codes::CheatCodeResource;
+use crate::runner::CollectedChars;
+
+/// Game data available to the argument completers
+pub struct CompletionContext<'a> {
+    pub cheat_codes: &'a CheatCodeResource,
+    pub collected_chars: &'a CollectedChars,
+}
+
+/// Values the argument of a command can take, filtered by the typed prefix afterward
+pub type ArgCompleter = fn(&CompletionContext) -> Vec<String>;
+
+/// A command of the terminal console.
+///
+/// The console only parses and dispatches the command as a [`ConsoleCommandEvent`],
+/// the plugin registering it handles the event.
+#[derive(Clone)]
+pub struct ConsoleCommand {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub args: Vec<ArgSpec>,
+    pub completer: Option<ArgCompleter>,
+}
+
+impl ConsoleCommand {
+    pub fn new(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            description,
+            args: Vec::new(),
+            completer: None,
+        }
+    }
+
+    pub fn with_arg(mut self, name: &'static str, kind: ArgKind) -> Self {
+        self.args.push(ArgSpec {
+            name,
+            kind,
+            optional: false,
+        });
+        self
+    }
+
+    /// Optional arguments must come after the required ones
+    pub fn with_optional_arg(mut self, name: &'static str, kind: ArgKind) -> Self {
+        self.args.push(ArgSpec {
+            name,
+            kind,
+            optional: true,
+        });
+        self
+    }
+
+    pub fn with_completer(mut self, completer: ArgCompleter) -> Self {
+        self.completer = Some(completer);
+        self
+    }
+
+    /// e.g. "cheat <code>", optional arguments between brackets
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.to_string();
+        for arg in self.args.iter() {
+            let name = if arg.kind == ArgKind::Rest {
+                format!("{}...", arg.name)
+            } else {
+                arg.name.to_string()
+            };
+            if arg.optional {
+                usage.push_str(&format!(" [{}]", name));
+            } else {
+                usage.push_str(&format!(" <{}>", name));
+            }
+        }
+        usage
+    }
+}
+
+/// Sent when the player enters a registered command, the arguments match its specs
+pub struct ConsoleCommandEvent {
+    pub name: String,
+    pub args: Vec<ArgValue>,
+}
+
+impl ConsoleCommandEvent {
+    pub fn text(&self, index: usize) -> Option<&str> {
+        match self.args.get(index) {
+            Some(ArgValue::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn integer(&self, index: usize) -> Option<i64> {
+        match self.args.get(index) {
+            Some(ArgValue::Integer(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self, index: usize) -> Option<f32> {
+        match self.args.get(index) {
+            Some(ArgValue::Number(value)) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Every command known by the console, sorted by name
+pub struct ConsoleCommands {
+    commands: BTreeMap<&'static str, ConsoleCommand>,
+}
+
+impl ConsoleCommands {
+    pub fn new() -> Self {
+        Self {
+            commands: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, command: ConsoleCommand) {
+        if self.commands.contains_key(command.name) {
+            warn!(
+                "[ConsolePlugin] Command \"{}\" registered twice, keeping the last one",
+                command.name
+            );
+        }
+        self.commands.insert(command.name, command);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleCommand> {
+        self.commands.values()
+    }
+
+    /// Lines listing the commands, used by the help and the welcome message
+    pub fn help_lines(&self) -> Vec<String> {
+        self.iter()
+            .map(|command| format!("    - {}: {}", command.usage(), command.description))
+            .collect()
+    }
+
+    /// Candidates for the last word of `input`: command names for the first word,
+    /// values of the command's completer afterward
+    pub fn complete(&self, input: &str, context: &CompletionContext) -> Vec<String> {
+        let words: Vec<&str> = input.split(' ').collect();
+        let prefix = words.last().copied().unwrap_or("");
+
+        let candidates: Vec<String> = if words.len() <= 1 {
+            self.iter()
+                .map(|command| command.name.to_string())
+                .collect()
+        } else {
+            match self.get(words[0]).and_then(|command| command.completer) {
+                Some(completer) => completer(context),
+                None => Vec::new(),
+            }
+        };
+
+        let mut candidates: Vec<String> = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(prefix))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+}
+
+pub trait AddConsoleCommand {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self;
+}
+
+impl AddConsoleCommand for App {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self {
+        if self.world.get_resource::<ConsoleCommands>().is_none() {
+            self.insert_resource(ConsoleCommands::new());
+        }
+        self.world
+            .get_resource_mut::<ConsoleCommands>()
+            .unwrap()
+            .add(command);
+        self
+    }
+}
+
+/// Texts of the cheat codes not activated yet the player has enough letters for
+pub fn complete_cheat_codes(context: &CompletionContext) -> Vec<String> {
+    context
+        .cheat_codes
+        .codes
+        .values()
+        .filter(|code| !context.cheat_codes.is_code_activated(&code.kind))
+        .filter(|code| {
+            let mut needed: HashMap<char, u32> = HashMap::new();
+            for c in code.text.chars() {
+                *needed.entry(c).or_insert(0) += 1;
+            }
+            needed.iter().all(|(c, count)| {
+                context
+                    .collected_chars
+                    .values_map
+                    .get(c)
+                    .copied()
+                    .unwrap_or(0)
+                    >= *count
+            })
+        })
+        .map(|code| code.text.clone())
+        .collect()
+}
+
+/// Longest prefix shared by all the candidates
+pub fn common_prefix(candidates: &[String]) -> String {
+    let first = match candidates.first() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+
+    let mut length = first.len();
+    for candidate in candidates.iter().skip(1) {
+        length = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((index, a), _)| index + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(length);
+    }
+    first[..length].to_string()
+}
@@ -0,0 +1,181 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::prelude::*;
+
+use super::{args::split_commands, registry::ConsoleCommandEvent};
+use super::{event::PrintToConsoleEvent, registry::CompletionContext};
+use crate::data;
+
+const SCRIPTS_DIR: &str = "assets/scripts";
+const SCRIPT_EXTENSION: &str = "txt";
+// guards against scripts running themselves
+const MAX_QUEUED_COMMANDS: usize = 256;
+const MAX_SCRIPTS_PER_LINE: usize = 32;
+
+/// Commands waiting to be run, one per frame so each one sees the effects of the
+/// previous ones, and the variables defined with `set`
+pub struct ConsoleScripts {
+    pub queue: VecDeque<String>,
+    pub variables: HashMap<String, String>,
+    // scripts started since the last line typed by the player
+    pub scripts_started: usize,
+}
+
+impl ConsoleScripts {
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            variables: HashMap::new(),
+            scripts_started: 0,
+        }
+    }
+
+    /// Replace the `$name` of the defined variables by their value
+    pub fn expand_variables(&self, command: &str) -> Result<String, String> {
+        let mut expanded = String::new();
+        let mut chars = command.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '$' {
+                expanded.push(c);
+                continue;
+            }
+
+            let mut name = String::new();
+            while let Some(next) = chars.peek() {
+                if next.is_alphanumeric() || *next == '_' {
+                    name.push(*next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            if name.is_empty() {
+                expanded.push(c);
+            } else if let Some(value) = self.variables.get(&name) {
+                expanded.push_str(value);
+            } else {
+                return Err(format!("unknown variable ${}", name));
+            }
+        }
+
+        Ok(expanded)
+    }
+}
+
+fn script_path(name: &str) -> String {
+    format!("{}/{}.{}", SCRIPTS_DIR, name, SCRIPT_EXTENSION)
+}
+
+/// Commands of a script, one or more per line, `#` starts a comment line
+pub fn load_script(name: &str) -> Result<Vec<String>, String> {
+    // scripts can only be read from the scripts folder
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!("invalid script name \"{}\"", name));
+    }
+
+    let bytes = data::read_data_file(&script_path(name))
+        .map_err(|_| format!("script \"{}\" not found", name))?;
+    let contents = String::from_utf8_lossy(&bytes);
+
+    let mut commands = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        commands.extend(split_commands(line).map_err(|err| format!("line {}: {}", i + 1, err))?);
+    }
+    Ok(commands)
+}
+
+/// Names of the scripts in the scripts folder
+pub fn complete_scripts(_context: &CompletionContext) -> Vec<String> {
+    let entries = match std::fs::read_dir(data::root_path().join(SCRIPTS_DIR)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .map_or(false, |ext| ext == SCRIPT_EXTENSION)
+        })
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .collect()
+}
+
+pub fn run_script_commands(
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    mut scripts: ResMut<ConsoleScripts>,
+) {
+    for event in command_reader.iter() {
+        match event.name.as_str() {
+            "run" => {
+                let name = event.text(0).unwrap_or_default();
+                match load_script(name) {
+                    Ok(commands) => {
+                        scripts.scripts_started += 1;
+                        if scripts.queue.len() + commands.len() > MAX_QUEUED_COMMANDS
+                            || scripts.scripts_started > MAX_SCRIPTS_PER_LINE
+                        {
+                            scripts.queue.clear();
+                            print_to_console.send(PrintToConsoleEvent(format!(
+                                "Error: script \"{}\" runs too many commands, stopping all scripts.",
+                                name
+                            )));
+                            continue;
+                        }
+                        info!("[ConsolePlugin] Running script {}", name);
+                        // the script runs before the commands following `run`
+                        for command in commands.into_iter().rev() {
+                            scripts.queue.push_front(command);
+                        }
+                    }
+                    Err(err) => {
+                        print_to_console.send(PrintToConsoleEvent(format!("Error: {}", err)));
+                    }
+                }
+            }
+            "set" => {
+                let name = event.text(0).unwrap_or_default();
+                let value = event.text(1).unwrap_or_default();
+                scripts
+                    .variables
+                    .insert(name.to_string(), value.to_string());
+            }
+            "echo" => {
+                let text = event.text(0).unwrap_or_default();
+                print_to_console.send(PrintToConsoleEvent(text.to_string()));
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn reset_console_scripts(mut scripts: ResMut<ConsoleScripts>) {
+    scripts.queue.clear();
+    scripts.variables.clear();
+}
+
+/// Scripts stop when the terminal is closed
+pub fn clear_script_queue(mut scripts: ResMut<ConsoleScripts>) {
+    if !scripts.queue.is_empty() {
+        info!(
+            "[ConsolePlugin] Dropping {} queued console commands",
+            scripts.queue.len()
+        );
+        scripts.queue.clear();
+    }
+}
@@ -0,0 +1,116 @@
+use bevy::{
+    input::{
+        keyboard::KeyboardInput,
+        mouse::{MouseScrollUnit, MouseWheel},
+    },
+    prelude::*,
+};
+
+use super::ConsoleData;
+
+// oldest lines are dropped past this count
+const MAX_SCROLLBACK_LINES: usize = 1000;
+pub const FONT_SIZE: f32 = 16.;
+// VT323 glyphs are 0.4em wide
+const CHAR_WIDTH: f32 = 0.4 * FONT_SIZE;
+// command input and gamepad picker, below the lines area
+const INPUT_HEIGHT: f32 = 40.;
+const WHEEL_ROWS: f32 = 3.;
+
+impl ConsoleData {
+    /// Add a message to the scrollback, one line per `\n`
+    pub fn push_line(&mut self, message: &str) {
+        self.lines
+            .extend(message.split('\n').map(|line| line.to_string()));
+        if self.lines.len() > MAX_SCROLLBACK_LINES {
+            let excess = self.lines.len() - MAX_SCROLLBACK_LINES;
+            self.lines.drain(..excess);
+        }
+        // new output brings the view back to the bottom
+        self.scroll = 0;
+        self.lines_dirty = true;
+    }
+
+    pub fn clear_lines(&mut self) {
+        self.lines.clear();
+        self.scroll = 0;
+        self.lines_dirty = true;
+    }
+
+    /// Positive values show older lines, clamped when the text is rebuilt
+    pub fn scroll_by(&mut self, rows: isize) {
+        self.scroll = (self.scroll as isize + rows).max(0) as usize;
+        self.lines_dirty = true;
+    }
+}
+
+/// Columns and rows of text fitting in the lines area, following the sizes used
+/// by `ui::build_ui`
+pub fn lines_area_size(window: &Window) -> (usize, usize) {
+    // padding percentages are relative to the window width
+    let padding = window.width() * 0.02 * 2.0;
+    let width = window.width() / 2.0 - padding;
+    let height = window.height() / 1.5 - padding - INPUT_HEIGHT;
+
+    (
+        ((width / CHAR_WIDTH) as usize).max(1),
+        ((height / FONT_SIZE) as usize).max(1),
+    )
+}
+
+/// Split a line into rows of at most `width` characters, breaking after the last
+/// space of each row when there is one
+pub fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut rest: Vec<char> = line.chars().collect();
+
+    while rest.len() > width {
+        match rest[..=width].iter().rposition(|c| *c == ' ') {
+            Some(space) if space > 0 => {
+                rows.push(rest[..space].iter().collect());
+                rest.drain(..=space);
+            }
+            _ => {
+                rows.push(rest[..width].iter().collect());
+                rest.drain(..width);
+            }
+        }
+    }
+    rows.push(rest.into_iter().collect());
+    rows
+}
+
+/// PageUp and PageDown scroll a page, the mouse wheel a few rows
+pub fn scroll_lines(
+    mut data: ResMut<ConsoleData>,
+    mut evr_keys: EventReader<KeyboardInput>,
+    mut evr_wheel: EventReader<MouseWheel>,
+    windows: Res<Windows>,
+) {
+    let (_, rows) = lines_area_size(windows.get_primary().unwrap());
+    let page = rows.saturating_sub(1).max(1) as isize;
+
+    let mut delta = 0;
+    for ev in evr_keys.iter() {
+        if !ev.state.is_pressed() {
+            continue;
+        }
+        match ev.key_code {
+            Some(KeyCode::PageUp) => delta += page,
+            Some(KeyCode::PageDown) => delta -= page,
+            _ => {}
+        }
+    }
+    for ev in evr_wheel.iter() {
+        let lines = match ev.unit {
+            MouseScrollUnit::Line => ev.y,
+            MouseScrollUnit::Pixel => ev.y / FONT_SIZE,
+        };
+        delta += (lines * WHEEL_ROWS) as isize;
+    }
+
+    if delta != 0 {
+        data.scroll_by(delta);
+    }
+}
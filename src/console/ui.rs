@@ -129,6 +129,16 @@ pub fn build_ui(
         ..Default::default()
     };
 
+    // gamepad character picker, hidden without a gamepad
+    let picker_component = TextBundle {
+        style: Style {
+            size: Size::new(Val::Percent(100.0), Val::Px(20.0)),
+            display: Display::None,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
     // Displaying cheat codes and collected letter in margins.
     // TODO: REFACTOR!! Duplicated code from second_page.rs
     let cheat_codes_content_container = NodeBundle {
@@ -284,6 +294,9 @@ pub fn build_ui(
                             // placeholder to be populated with the command input
                             parent.spawn_bundle(command_component).insert(CommandInput);
                         });
+                    parent
+                        .spawn_bundle(picker_component)
+                        .insert(super::picker::CharPickerText);
                 });
             // foreground
             parent
@@ -0,0 +1,492 @@
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use ron::de::from_bytes;
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::{GameAudioOptions, GameAudioState},
+    data,
+    pause_menu::button::{UIButton, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON},
+    states::GameStates,
+};
+
+pub mod keys;
+mod ui;
+
+const SETTINGS_FILE: &str = "settings.ron";
+
+/// Everything the player can do with a key, the actual key is looked up in [`InputBindings`]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Action {
+    MoveRight,
+    MoveLeft,
+    Jump,
+    Crouch,
+    Dash,
+    Attack,
+    Interact,
+    TabMenu,
+    // also closes the menus
+    Pause,
+    // only listed in `ALL` with the dev-console feature, but always known so a settings
+    // file saved by a dev build still loads
+    GameOver,
+}
+
+impl Action {
+    pub const ALL: &'static [Action] = &[
+        Action::MoveRight,
+        Action::MoveLeft,
+        Action::Jump,
+        Action::Crouch,
+        Action::Dash,
+        Action::Attack,
+        Action::Interact,
+        Action::TabMenu,
+        Action::Pause,
+        #[cfg(feature = "dev-console")]
+        Action::GameOver,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveRight => "Move right",
+            Action::MoveLeft => "Move left",
+            Action::Jump => "Jump",
+            Action::Crouch => "Crouch",
+            Action::Dash => "Dash",
+            Action::Attack => "Attack",
+            Action::Interact => "Interact",
+            Action::TabMenu => "Tab menu",
+            Action::Pause => "Pause",
+            Action::GameOver => "Game over",
+        }
+    }
+
+    fn default_key(&self) -> KeyCode {
+        match self {
+            Action::MoveRight => KeyCode::D,
+            Action::MoveLeft => KeyCode::A,
+            Action::Jump => KeyCode::Space,
+            Action::Crouch => KeyCode::LControl,
+            Action::Dash => KeyCode::LShift,
+            Action::Attack => KeyCode::Return,
+            Action::Interact => KeyCode::E,
+            Action::TabMenu => KeyCode::Tab,
+            Action::Pause => KeyCode::Escape,
+            Action::GameOver => KeyCode::G,
+        }
+    }
+
+    // gamepad buttons are not remappable
+    fn gamepad_button(&self) -> Option<GamepadButtonType> {
+        match self {
+            Action::MoveRight => Some(GamepadButtonType::DPadRight),
+            Action::MoveLeft => Some(GamepadButtonType::DPadLeft),
+            Action::Jump => Some(GamepadButtonType::South),
+            Action::Crouch => Some(GamepadButtonType::DPadDown),
+            Action::Dash => Some(GamepadButtonType::East),
+            Action::Attack => Some(GamepadButtonType::West),
+            Action::Interact => Some(GamepadButtonType::North),
+            Action::TabMenu => Some(GamepadButtonType::Select),
+            Action::Pause => Some(GamepadButtonType::Start),
+            Action::GameOver => None,
+        }
+    }
+}
+
+// stick values below this are ignored
+const STICK_DEAD_ZONE: f32 = 0.2;
+// stick values above this count as a pressed direction
+const STICK_PRESS_THRESHOLD: f32 = 0.5;
+
+/// Settings saved in the user data folder, keys are saved by name
+#[derive(Serialize, Deserialize, Default)]
+struct SettingsFile {
+    #[serde(default)]
+    bindings: BTreeMap<Action, String>,
+}
+
+pub struct InputBindings {
+    pub path: PathBuf,
+    keys: HashMap<Action, KeyCode>,
+}
+
+impl InputBindings {
+    pub fn new(path: PathBuf) -> Self {
+        let mut bindings = Self {
+            path,
+            keys: HashMap::new(),
+        };
+        bindings.reset_to_default();
+        bindings
+    }
+
+    /// Default bindings overridden by the ones saved at `path`
+    pub fn load(path: PathBuf) -> Self {
+        let mut bindings = Self::new(path);
+
+        let file = match std::fs::read(&bindings.path) {
+            Ok(bytes) => match from_bytes::<SettingsFile>(&bytes) {
+                Ok(file) => file,
+                Err(err) => {
+                    error!(
+                        "[ControlsPlugin] Unable to parse {}: {}",
+                        bindings.path.display(),
+                        err
+                    );
+                    return bindings;
+                }
+            },
+            Err(_) => return bindings,
+        };
+
+        for (action, name) in file.bindings.iter() {
+            match keys::key_from_name(name) {
+                Some(key) => {
+                    bindings.keys.insert(*action, key);
+                }
+                None => warn!(
+                    "[ControlsPlugin] Unknown key \"{}\" bound to {:?}, using the default key",
+                    name, action
+                ),
+            }
+        }
+
+        bindings
+    }
+
+    pub fn save(&self) {
+        let file = SettingsFile {
+            bindings: self
+                .keys
+                .iter()
+                .map(|(action, key)| (*action, keys::key_name(*key)))
+                .collect(),
+        };
+
+        let result = to_string_pretty(&file, PrettyConfig::new())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                data::write_user_file(&self.path, &contents).map_err(|err| err.to_string())
+            });
+
+        if let Err(err) = result {
+            error!(
+                "[ControlsPlugin] Unable to save the settings in {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.keys
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_key())
+    }
+
+    /// Bind `key` to `action`, the action previously using this key gets the old key
+    /// of `action` so no key is bound twice
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        let previous_key = self.key(action);
+        if let Some(other_action) = Action::ALL
+            .iter()
+            .find(|other| **other != action && self.key(**other) == key)
+        {
+            self.keys.insert(*other_action, previous_key);
+        }
+        self.keys.insert(action, key);
+    }
+
+    pub fn reset_to_default(&mut self) {
+        for action in Action::ALL.iter() {
+            self.keys.insert(*action, action.default_key());
+        }
+    }
+}
+
+/// Keyboard and gamepad state read through the player's bindings
+#[derive(SystemParam)]
+pub struct ActionInput<'w, 's> {
+    keyboard: ResMut<'w, Input<KeyCode>>,
+    bindings: Res<'w, InputBindings>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: ResMut<'w, Input<GamepadButton>>,
+    gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> ActionInput<'w, 's> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.keyboard.pressed(self.bindings.key(action))
+            || self.button_state(action, |buttons, button| buttons.pressed(button))
+            || match action {
+                Action::MoveRight => {
+                    self.stick(GamepadAxisType::LeftStickX) > STICK_PRESS_THRESHOLD
+                }
+                Action::MoveLeft => {
+                    self.stick(GamepadAxisType::LeftStickX) < -STICK_PRESS_THRESHOLD
+                }
+                Action::Crouch => self.stick(GamepadAxisType::LeftStickY) < -STICK_PRESS_THRESHOLD,
+                _ => false,
+            }
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.keyboard.just_pressed(self.bindings.key(action))
+            || self.button_state(action, |buttons, button| buttons.just_pressed(button))
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.keyboard.just_released(self.bindings.key(action))
+            || self.button_state(action, |buttons, button| buttons.just_released(button))
+    }
+
+    /// Horizontal movement between -1 and 1, the left stick is analog while keys and
+    /// the D-pad are all or nothing
+    pub fn move_axis(&self) -> f32 {
+        let stick = self.stick(GamepadAxisType::LeftStickX);
+        if stick.abs() > STICK_DEAD_ZONE {
+            return stick.clamp(-1.0, 1.0);
+        }
+
+        let right = self.keyboard.pressed(self.bindings.key(Action::MoveRight))
+            || self.button_state(Action::MoveRight, |buttons, button| buttons.pressed(button));
+        let left = self.keyboard.pressed(self.bindings.key(Action::MoveLeft))
+            || self.button_state(Action::MoveLeft, |buttons, button| buttons.pressed(button));
+        (right as i8 - left as i8) as f32
+    }
+
+    /// Consume the input so the next state doesn't see it this frame
+    pub fn reset(&mut self, action: Action) {
+        let key = self.bindings.key(action);
+        self.keyboard.reset(key);
+        if let Some(button_type) = action.gamepad_button() {
+            for gamepad in self.gamepads.iter() {
+                self.gamepad_buttons
+                    .reset(GamepadButton(*gamepad, button_type));
+            }
+        }
+    }
+
+    pub fn key_name(&self, action: Action) -> String {
+        keys::key_name(self.bindings.key(action))
+    }
+
+    /// Key or button to show in prompts, depending on what the player is using
+    pub fn prompt_name(&self, action: Action) -> String {
+        match action.gamepad_button() {
+            Some(button_type) if self.has_gamepad() => keys::button_name(button_type).to_string(),
+            _ => self.key_name(action),
+        }
+    }
+
+    /// Replace the actions written between braces, e.g. `{Jump}`, with their prompt name
+    pub fn fill_prompts(&self, text: &str) -> String {
+        Action::ALL.iter().fold(text.to_string(), |text, action| {
+            text.replace(&format!("{{{:?}}}", action), &self.prompt_name(*action))
+        })
+    }
+
+    pub fn has_gamepad(&self) -> bool {
+        self.gamepads.iter().next().is_some()
+    }
+
+    /// Menu focus movement: -1 for up/left, 1 for down/right.
+    /// Arrow keys and the D-pad always navigate, whatever the bindings
+    pub fn menu_step(&self) -> i8 {
+        let previous = self.keyboard.just_pressed(KeyCode::Up)
+            || self.keyboard.just_pressed(KeyCode::Left)
+            || self
+                .any_button_just_pressed(&[GamepadButtonType::DPadUp, GamepadButtonType::DPadLeft]);
+        let next = self.keyboard.just_pressed(KeyCode::Down)
+            || self.keyboard.just_pressed(KeyCode::Right)
+            || self.any_button_just_pressed(&[
+                GamepadButtonType::DPadDown,
+                GamepadButtonType::DPadRight,
+            ]);
+        next as i8 - previous as i8
+    }
+
+    /// Click the focused menu button
+    pub fn menu_confirm(&self) -> bool {
+        self.keyboard.just_pressed(KeyCode::Return)
+            || self.any_button_just_pressed(&[GamepadButtonType::South])
+    }
+
+    /// Consume the confirm input so the clicked button doesn't see it
+    pub fn reset_menu_confirm(&mut self) {
+        self.keyboard.reset(KeyCode::Return);
+        for gamepad in self.gamepads.iter() {
+            self.gamepad_buttons
+                .reset(GamepadButton(*gamepad, GamepadButtonType::South));
+        }
+    }
+
+    pub fn any_button_just_pressed(&self, button_types: &[GamepadButtonType]) -> bool {
+        self.gamepads.iter().any(|gamepad| {
+            button_types.iter().any(|button_type| {
+                self.gamepad_buttons
+                    .just_pressed(GamepadButton(*gamepad, *button_type))
+            })
+        })
+    }
+
+    fn button_state(
+        &self,
+        action: Action,
+        state: impl Fn(&Input<GamepadButton>, GamepadButton) -> bool,
+    ) -> bool {
+        match action.gamepad_button() {
+            Some(button_type) => self
+                .gamepads
+                .iter()
+                .any(|gamepad| state(&self.gamepad_buttons, GamepadButton(*gamepad, button_type))),
+            None => false,
+        }
+    }
+
+    // strongest value of the axis among the connected gamepads
+    fn stick(&self, axis_type: GamepadAxisType) -> f32 {
+        self.gamepads
+            .iter()
+            .filter_map(|gamepad| self.gamepad_axes.get(GamepadAxis(*gamepad, axis_type)))
+            .fold(0.0, |strongest: f32, value| {
+                if value.abs() > strongest.abs() {
+                    value
+                } else {
+                    strongest
+                }
+            })
+    }
+}
+
+/// Action waiting for a key press on the controls page
+pub struct RebindState {
+    pub action: Option<Action>,
+}
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputBindings::load(
+            data::user_data_path().join(SETTINGS_FILE),
+        ));
+        app.insert_resource(RebindState { action: None });
+
+        app.add_system_set(
+            SystemSet::on_enter(GameStates::Controls)
+                .with_system(ui::build_ui)
+                .label("controls_build"),
+        );
+        app.add_system_set(
+            SystemSet::on_update(GameStates::Controls)
+                .with_system(button_handler)
+                // the pressed key must not also move the menu focus
+                .with_system(rebind_key.before("navigate_buttons"))
+                .with_system(close_controls)
+                .with_system(ui::update_labels.after("controls_build")),
+        );
+        app.add_system_set(SystemSet::on_exit(GameStates::Controls).with_system(destroy_menu));
+    }
+}
+
+fn destroy_menu(
+    mut commands: Commands,
+    query: Query<Entity, With<ui::ControlsEntity>>,
+    mut rebind_state: ResMut<RebindState>,
+) {
+    rebind_state.action = None;
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn close_controls(
+    mut actions: ActionInput,
+    rebind_state: Res<RebindState>,
+    mut game_state: ResMut<State<GameStates>>,
+) {
+    if rebind_state.action.is_none() && actions.just_pressed(Action::Pause) {
+        game_state.pop().unwrap();
+        actions.reset(Action::Pause);
+    }
+}
+
+/// Assign the next pressed key to the action waiting for one, Escape cancels
+fn rebind_key(
+    mut keyboard: ResMut<Input<KeyCode>>,
+    mut rebind_state: ResMut<RebindState>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let action = match rebind_state.action {
+        Some(action) => action,
+        None => return,
+    };
+
+    let pressed_key = keyboard
+        .get_just_pressed()
+        .copied()
+        .find(|key| keys::is_bindable(*key));
+    if let Some(key) = pressed_key {
+        if key != KeyCode::Escape {
+            bindings.bind(action, key);
+            bindings.save();
+            info!("[ControlsPlugin] {:?} bound to {:?}", action, key);
+        }
+        rebind_state.action = None;
+        keyboard.reset(key);
+    }
+}
+
+fn button_handler(
+    mut interaction_query: Query<(&Interaction, &mut UiColor, &UIButton), Changed<Interaction>>,
+    mut game_state: ResMut<State<GameStates>>,
+    mut game_audio_state: ResMut<GameAudioState>,
+    mut rebind_state: ResMut<RebindState>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    for (interaction, mut color, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                game_audio_state.queue_sound(
+                    "button-sound".to_owned(),
+                    GameAudioOptions {
+                        ..Default::default()
+                    },
+                );
+                *color = PRESSED_BUTTON.into();
+                match button.name.as_str() {
+                    "back" => {
+                        game_state.pop().unwrap();
+                    }
+                    "reset" => {
+                        bindings.reset_to_default();
+                        bindings.save();
+                    }
+                    name => {
+                        rebind_state.action = Action::ALL
+                            .iter()
+                            .find(|action| ui::button_name(**action) == name)
+                            .copied();
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
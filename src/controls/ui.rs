@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use super::{keys, Action, InputBindings, RebindState};
+use crate::pause_menu::button::{UIButton, NORMAL_BUTTON};
+
+#[derive(Component)]
+pub struct ControlsEntity;
+
+// key shown on the button of an action
+#[derive(Component)]
+pub struct ActionKeyText(Action);
+
+pub fn button_name(action: Action) -> String {
+    format!("bind_{:?}", action)
+}
+
+pub fn build_ui(
+    mut commands: Commands,
+    window: Res<Windows>,
+    bindings: Res<InputBindings>,
+    asset_server: Res<AssetServer>,
+) {
+    info!("[ControlsPlugin] Building UI");
+
+    let font_handle: Handle<Font> = asset_server.load("fonts/VT323-Regular.ttf");
+
+    let current_window = window.get_primary().unwrap();
+
+    // UI comps
+    let parent_component = NodeBundle {
+        style: Style {
+            size: Size::new(
+                Val::Px(current_window.width()),
+                Val::Px(current_window.height()),
+            ),
+            flex_direction: FlexDirection::ColumnReverse,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        color: Color::rgba_u8(0, 0, 0, 220).into(),
+        ..Default::default()
+    };
+
+    let title_text = TextBundle {
+        text: Text::with_section(
+            "Controls",
+            TextStyle {
+                font_size: 64.,
+                font: font_handle.clone(),
+                color: Color::rgb_u8(220, 220, 220).into(),
+            },
+            TextAlignment {
+                ..Default::default()
+            },
+        ),
+        ..Default::default()
+    };
+
+    let text_style = TextStyle {
+        font_size: 32.,
+        font: font_handle.clone(),
+        color: Color::rgb(0.9, 0.9, 0.9),
+    };
+
+    let btn_group = NodeBundle {
+        style: Style {
+            margin: Rect {
+                top: Val::Px(20.),
+                ..Default::default()
+            },
+            flex_direction: FlexDirection::Row,
+            ..Default::default()
+        },
+        color: Color::rgba_u8(0, 0, 0, 0).into(),
+        ..Default::default()
+    };
+
+    let reset_btn = UIButton::new(
+        "Defaults".to_string(),
+        font_handle.clone(),
+        "reset".to_string(),
+    );
+    let back_btn = UIButton::new("Back".to_string(), font_handle.clone(), "back".to_string());
+
+    // building tree
+    commands
+        .spawn_bundle(parent_component)
+        .with_children(|parent| {
+            parent.spawn_bundle(title_text);
+
+            // one row per action, clicking it waits for the new key
+            for action in Action::ALL.iter() {
+                parent
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(420.0), Val::Px(40.0)),
+                            margin: Rect::all(Val::Px(4.)),
+                            padding: Rect {
+                                left: Val::Px(15.),
+                                right: Val::Px(15.),
+                                ..Default::default()
+                            },
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        color: NORMAL_BUTTON.into(),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::with_section(
+                                action.label(),
+                                text_style.clone(),
+                                Default::default(),
+                            ),
+                            ..Default::default()
+                        });
+                        parent
+                            .spawn_bundle(TextBundle {
+                                text: Text::with_section(
+                                    keys::key_name(bindings.key(*action)),
+                                    text_style.clone(),
+                                    Default::default(),
+                                ),
+                                ..Default::default()
+                            })
+                            .insert(ActionKeyText(*action));
+                    })
+                    .insert(UIButton::new(
+                        action.label().to_string(),
+                        font_handle.clone(),
+                        button_name(*action),
+                    ));
+            }
+
+            parent.spawn_bundle(btn_group).with_children(|parent| {
+                reset_btn.spawn(parent);
+                back_btn.spawn(parent);
+            });
+        })
+        .insert(ControlsEntity);
+
+    info!("[ControlsPlugin] UI constructed");
+}
+
+pub fn update_labels(
+    bindings: Res<InputBindings>,
+    rebind_state: Res<RebindState>,
+    mut query: Query<(&mut Text, &ActionKeyText)>,
+) {
+    if !bindings.is_changed() && !rebind_state.is_changed() {
+        return;
+    }
+
+    for (mut text, ActionKeyText(action)) in query.iter_mut() {
+        text.sections[0].value = if rebind_state.action == Some(*action) {
+            "press a key...".to_string()
+        } else {
+            keys::key_name(bindings.key(*action))
+        };
+    }
+}
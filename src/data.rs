@@ -0,0 +1,55 @@
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+};
+
+/// Root folder of the game files (`assets/`, `data/`), resolved the same way as the
+/// `AssetServer` does: next to the manifest when started through cargo, next to the
+/// executable otherwise.
+pub fn root_path() -> PathBuf {
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        PathBuf::from(manifest_dir)
+    } else {
+        env::current_exe()
+            .ok()
+            .and_then(|path| path.parent().map(|parent| parent.to_path_buf()))
+            .unwrap_or_default()
+    }
+}
+
+/// Read a file shipped with the game, `path` being relative to the root folder
+pub fn read_data_file(path: &str) -> io::Result<Vec<u8>> {
+    std::fs::read(root_path().join(path))
+}
+
+/// Folder where the player's files (high scores, settings) are saved, following the
+/// platform conventions. Can be overridden with the `CHEATERS_NEVER_WIN_DATA_DIR`
+/// environment variable, to keep the real files untouched while testing.
+pub fn user_data_path() -> PathBuf {
+    if let Ok(path) = env::var("CHEATERS_NEVER_WIN_DATA_DIR") {
+        return PathBuf::from(path);
+    }
+
+    let base_path = if cfg!(target_os = "windows") {
+        env::var("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+
+    // fall back on the game folder when the platform folder is unknown
+    base_path
+        .map(|path| path.join("cheaters_never_win"))
+        .unwrap_or_else(|_| root_path())
+}
+
+/// Write a file in the user data folder, creating the folder if needed
+pub fn write_user_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
@@ -1,4 +1,4 @@
-use crate::states::GameStates;
+use crate::{run::NewRunStage, states::GameStates};
 use bevy::prelude::*;
 pub struct EffectsPlugin;
 
@@ -9,6 +9,7 @@ impl Plugin for EffectsPlugin {
                 .with_system(animate_effect)
                 .after("setup_physics"),
         );
+        app.add_system_to_stage(NewRunStage, despawn_effects);
     }
 }
 
@@ -48,6 +49,12 @@ pub fn spawn_explosion(
         .insert(Name::new("Explosion"));
 }
 
+fn despawn_effects(mut commands: Commands, effect_query: Query<Entity, With<OneShotEffect>>) {
+    for entity in effect_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 fn animate_effect(
     mut commands: Commands,
     time: Res<Time>,
@@ -1,7 +1,7 @@
 use crate::{physics::jump, states::GameStates};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 pub struct EnemiesPlugin;
 
@@ -13,13 +13,27 @@ impl Plugin for EnemiesPlugin {
     }
 }
 
-#[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, Hash, PartialEq, Eq, Clone)]
 pub enum EnemyKind {
     Slime,
 }
 
+impl EnemyKind {
+    #[cfg(feature = "dev-console")]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "slime" => Some(EnemyKind::Slime),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Component)]
-pub struct Enemy;
+pub struct Enemy {
+    pub health: f32,
+    // added to the run score when killed
+    pub score: usize,
+}
 
 #[derive(Debug, Component)]
 pub struct SlimeEnemy {
@@ -80,7 +94,10 @@ fn spawn_slime(
             ..Default::default()
         })
         .insert(ColliderPositionSync::Discrete)
-        .insert(Enemy)
+        .insert(Enemy {
+            health: 2.0,
+            score: 10,
+        })
         .insert(SlimeEnemy {
             jump_timer: Timer::from_seconds(3.0, true),
             jump_impulse: 550.0,
@@ -1,7 +1,10 @@
 use bevy::{app::AppExit, prelude::*};
 
+#[cfg(feature = "dev-console")]
+use crate::controls::{Action, ActionInput};
 use crate::{
     pause_menu::button::{UIButton, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON},
+    run::NewRunEvent,
     states::GameStates,
 };
 
@@ -17,19 +20,19 @@ impl Plugin for GameOverPlugin {
         app.add_system_set(SystemSet::on_update(GameStates::GameOver).with_system(button_handler));
         app.add_system_set(SystemSet::on_exit(GameStates::GameOver).with_system(ui_destroyer));
 
+        #[cfg(feature = "dev-console")]
         app.add_system_set(
             SystemSet::on_update(GameStates::Main).with_system(open_gameover_screen),
         );
     }
 }
 
-fn open_gameover_screen(
-    mut keyboard: ResMut<Input<KeyCode>>,
-    mut game_state: ResMut<State<GameStates>>,
-) {
-    if keyboard.just_pressed(KeyCode::G) {
-        game_state.push(GameStates::GameOver).unwrap();
-        keyboard.reset(KeyCode::G);
+#[cfg(feature = "dev-console")]
+fn open_gameover_screen(mut actions: ActionInput, mut game_state: ResMut<State<GameStates>>) {
+    if actions.just_pressed(Action::GameOver) {
+        // the player may have died this frame, the state is then already queued
+        let _ = game_state.push(GameStates::GameOver);
+        actions.reset(Action::GameOver);
     }
 }
 
@@ -41,7 +44,7 @@ fn ui_destroyer(mut commands: Commands, query: Query<Entity, With<GameOverScreen
 
 pub fn button_handler(
     mut interaction_query: Query<(&Interaction, &mut UiColor, &UIButton), Changed<Interaction>>,
-    mut game_state: ResMut<State<GameStates>>,
+    mut new_run_writer: EventWriter<NewRunEvent>,
     mut exit: EventWriter<AppExit>,
 ) {
     for (interaction, mut color, button) in interaction_query.iter_mut() {
@@ -50,7 +53,7 @@ pub fn button_handler(
                 *color = PRESSED_BUTTON.into();
                 match button.name.as_str() {
                     "restart" => {
-                        game_state.set(GameStates::Main).unwrap();
+                        new_run_writer.send(NewRunEvent { seed: None });
                     }
                     "quit" => {
                         //game_state.set(GameStates::MainMenu).unwrap();
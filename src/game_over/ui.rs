@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{pause_menu::button::UIButton, stats::GameStatsResource};
+use crate::{pause_menu::button::UIButton, run::RunSeed, stats::GameStatsResource};
 
 #[derive(Component)]
 pub struct GameOverScreenComponent;
@@ -9,6 +9,7 @@ pub fn build_ui(
     mut commands: Commands,
     window: Res<Windows>,
     stats_res: Res<GameStatsResource>,
+    run_seed: Res<RunSeed>,
     asset_server: Res<AssetServer>,
 ) {
     let font_handle = asset_server.load("fonts/VT323-Regular.ttf");
@@ -62,6 +63,21 @@ pub fn build_ui(
         ..Default::default()
     };
 
+    let seed_text = TextBundle {
+        text: Text::with_section(
+            format!("Seed : {}", run_seed.seed),
+            TextStyle {
+                font_size: 32.,
+                font: font_handle.clone(),
+                color: Color::rgb_u8(180, 180, 180).into(),
+            },
+            TextAlignment {
+                ..Default::default()
+            },
+        ),
+        ..Default::default()
+    };
+
     let btn_group = NodeBundle {
         style: Style {
             margin: Rect {
@@ -76,11 +92,11 @@ pub fn build_ui(
         ..Default::default()
     };
 
-    /*let restart_btn = UIButton::new(
+    let restart_btn = UIButton::new(
         "Restart".to_string(),
         font_handle.clone(),
         "restart".to_string(),
-    );*/
+    );
     let quit_btn = UIButton::new(
         "Quit game".to_string(),
         font_handle.clone(),
@@ -93,8 +109,9 @@ pub fn build_ui(
         .with_children(|parent| {
             parent.spawn_bundle(game_over_text);
             parent.spawn_bundle(score_text);
+            parent.spawn_bundle(seed_text);
             parent.spawn_bundle(btn_group).with_children(|parent| {
-                //restart_btn.spawn(parent);
+                restart_btn.spawn(parent);
                 quit_btn.spawn(parent);
             });
         })
@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use ron::de::from_bytes;
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::{GameAudioOptions, GameAudioState},
+    cheat_codes::{CheatCodeKind, CheatCodeResource},
+    data,
+    pause_menu::button::{UIButton, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON},
+    run::RunSeed,
+    states::GameStates,
+    stats::GameStatsResource,
+};
+
+mod ui;
+
+const HIGH_SCORES_FILE: &str = "high_scores.ron";
+// number of runs shown on the high scores screen
+pub const HIGH_SCORES_SHOWN: usize = 10;
+
+#[derive(Component)]
+pub struct HighScoresEntity;
+
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HighScoresResource::load(
+            data::user_data_path().join(HIGH_SCORES_FILE),
+        ));
+        app.add_system_set(SystemSet::on_enter(GameStates::GameOver).with_system(record_run));
+
+        app.add_system_set(SystemSet::on_enter(GameStates::HighScores).with_system(ui::build_ui));
+        app.add_system_set(
+            SystemSet::on_update(GameStates::HighScores).with_system(button_handler),
+        );
+        app.add_system_set(SystemSet::on_exit(GameStates::HighScores).with_system(destroy_menu));
+    }
+}
+
+/// A finished run, as saved in the high scores file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RunRecord {
+    pub score: usize,
+    pub distance: f32,
+    pub run_time: f64,
+    pub avg_speed: f32,
+    pub cheats_activated: Vec<CheatCodeKind>,
+    pub seed: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct HighScoresFile {
+    runs: Vec<RunRecord>,
+}
+
+/// History of every finished run, saved after each game over
+pub struct HighScoresResource {
+    pub path: PathBuf,
+    pub runs: Vec<RunRecord>,
+}
+
+impl HighScoresResource {
+    /// Load the runs saved at `path`, starting with an empty history if the file
+    /// doesn't exist yet or can't be read
+    pub fn load(path: PathBuf) -> Self {
+        let runs = match std::fs::read(&path) {
+            Ok(bytes) => match from_bytes::<HighScoresFile>(&bytes) {
+                Ok(file) => file.runs,
+                Err(err) => {
+                    error!(
+                        "[HighScoresPlugin] Unable to parse {}: {}",
+                        path.display(),
+                        err
+                    );
+                    Vec::new()
+                }
+            },
+            Err(_) => {
+                info!(
+                    "[HighScoresPlugin] No high scores saved in {}",
+                    path.display()
+                );
+                Vec::new()
+            }
+        };
+
+        Self { path, runs }
+    }
+
+    pub fn save(&self) {
+        let file = HighScoresFile {
+            runs: self.runs.clone(),
+        };
+
+        let result = to_string_pretty(&file, PrettyConfig::new())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                data::write_user_file(&self.path, &contents).map_err(|err| err.to_string())
+            });
+
+        match result {
+            Ok(()) => info!("[HighScoresPlugin] Runs saved in {}", self.path.display()),
+            Err(err) => error!(
+                "[HighScoresPlugin] Unable to save the runs in {}: {}",
+                self.path.display(),
+                err
+            ),
+        }
+    }
+
+    pub fn add_run(&mut self, run: RunRecord) {
+        self.runs.push(run);
+        self.save();
+    }
+
+    /// Best runs first, ties are broken by the oldest run
+    pub fn best_runs(&self, count: usize) -> Vec<&RunRecord> {
+        let mut runs = self.runs.iter().collect::<Vec<&RunRecord>>();
+        runs.sort_by(|a, b| b.score.cmp(&a.score));
+        runs.truncate(count);
+        runs
+    }
+}
+
+fn record_run(
+    mut high_scores: ResMut<HighScoresResource>,
+    stats_res: Res<GameStatsResource>,
+    cheat_codes: Res<CheatCodeResource>,
+    run_seed: Res<RunSeed>,
+) {
+    high_scores.add_run(RunRecord {
+        score: stats_res.get_score(),
+        distance: stats_res.distance,
+        run_time: stats_res.run_time,
+        avg_speed: stats_res.avg_speed,
+        cheats_activated: cheat_codes.activated().to_vec(),
+        seed: run_seed.seed,
+    });
+}
+
+fn destroy_menu(mut commands: Commands, query: Query<Entity, With<HighScoresEntity>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn button_handler(
+    mut interaction_query: Query<(&Interaction, &mut UiColor, &UIButton), Changed<Interaction>>,
+    mut game_state: ResMut<State<GameStates>>,
+    mut game_audio_state: ResMut<GameAudioState>,
+) {
+    for (interaction, mut color, button) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                game_audio_state.queue_sound(
+                    "button-sound".to_owned(),
+                    GameAudioOptions {
+                        ..Default::default()
+                    },
+                );
+                *color = PRESSED_BUTTON.into();
+                if button.name == "back" {
+                    game_state.set(GameStates::MainMenu).unwrap();
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
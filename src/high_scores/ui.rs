@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+
+use super::{HighScoresEntity, HighScoresResource, HIGH_SCORES_SHOWN};
+use crate::pause_menu::button::UIButton;
+
+pub fn build_ui(
+    mut commands: Commands,
+    window: Res<Windows>,
+    high_scores: Res<HighScoresResource>,
+    asset_server: Res<AssetServer>,
+) {
+    info!("[HighScoresPlugin] Building UI");
+
+    let font_handle: Handle<Font> = asset_server.load("fonts/VT323-Regular.ttf");
+
+    let current_window = window.get_primary().unwrap();
+
+    // UI comps
+    let parent_component = NodeBundle {
+        style: Style {
+            size: Size::new(
+                Val::Px(current_window.width()),
+                Val::Px(current_window.height()),
+            ),
+            flex_direction: FlexDirection::ColumnReverse,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        color: Color::rgba_u8(0, 0, 0, 220).into(),
+        ..Default::default()
+    };
+
+    let title_text = TextBundle {
+        text: Text::with_section(
+            "High Scores",
+            TextStyle {
+                font_size: 72.,
+                font: font_handle.clone(),
+                color: Color::rgb_u8(220, 220, 220).into(),
+            },
+            TextAlignment {
+                ..Default::default()
+            },
+        ),
+        ..Default::default()
+    };
+
+    let row_style = TextStyle {
+        font_size: 28.,
+        font: font_handle.clone(),
+        color: Color::rgb_u8(200, 200, 200).into(),
+    };
+
+    let mut rows = vec![format!(
+        "{:<4}{:>8}{:>12}{:>10}{:>10}{:>8}  {}",
+        "#", "score", "distance", "time", "speed", "cheats", "seed"
+    )];
+    for (i, run) in high_scores.best_runs(HIGH_SCORES_SHOWN).iter().enumerate() {
+        rows.push(format!(
+            "{:<4}{:>8}{:>12.1}{:>9.0}s{:>10.1}{:>8}  {}",
+            i + 1,
+            run.score,
+            run.distance,
+            run.run_time,
+            run.avg_speed,
+            run.cheats_activated.len(),
+            run.seed
+        ));
+    }
+    if rows.len() == 1 {
+        rows.push("No run finished yet".to_string());
+    }
+
+    let back_btn = UIButton::new("Back".to_string(), font_handle.clone(), "back".to_string());
+
+    // building tree
+    commands
+        .spawn_bundle(parent_component)
+        .with_children(|parent| {
+            parent.spawn_bundle(title_text);
+            for row in rows.into_iter() {
+                parent.spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        row,
+                        row_style.clone(),
+                        TextAlignment {
+                            ..Default::default()
+                        },
+                    ),
+                    ..Default::default()
+                });
+            }
+            back_btn.spawn(parent);
+        })
+        .insert(HighScoresEntity);
+
+    info!("[HighScoresPlugin] UI constructed");
+}
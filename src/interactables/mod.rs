@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+
+use crate::run::NewRunStage;
+
 mod chars;
 mod terminal;
 
@@ -18,6 +21,17 @@ pub struct InteractablesPlugin;
 impl Plugin for InteractablesPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(terminal::InteractableTerminalPlugin);
+        app.add_system_to_stage(NewRunStage, despawn_all_interactables);
+    }
+}
+
+/// Interactables have no rigid body so they survive the player despawn, clean them here
+fn despawn_all_interactables(
+    mut commands: Commands,
+    query: Query<Entity, With<InteractableComponent>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
     }
 }
 
@@ -0,0 +1,27 @@
+//! Game modules, shared by the game and the tools in `src/bin`
+
+pub mod audio;
+pub mod camera;
+pub mod cheat_codes;
+#[cfg(debug_assertions)]
+pub mod chunk_editor;
+pub mod console;
+pub mod controls;
+pub mod data;
+pub mod effects;
+pub mod enemies;
+pub mod game_over;
+pub mod high_scores;
+pub mod interactables;
+pub mod letter_gutter;
+pub mod lore;
+pub mod main_menu;
+pub mod pause_menu;
+pub mod physics;
+pub mod platforms;
+pub mod run;
+pub mod runner;
+pub mod states;
+pub mod stats;
+pub mod tab_menu;
+pub mod toast;
@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use ron::de::from_bytes;
+use serde::Deserialize;
+
+use crate::cheat_codes::CheatCodeKind;
+use crate::data;
+
+pub const LOGS_PATH: &str = "data/logs.ron";
+
+/// What the player must do to read a log, checked during every run
+#[derive(Deserialize, Clone, Debug)]
+pub enum UnlockCondition {
+    Always,
+    // distance of a single run, as shown by the `status` command
+    Distance(f32),
+    CheatActivated(CheatCodeKind),
+    // terminals opened across every run
+    TerminalsVisited(usize),
+}
+
+/// Entry of the lore, as written in `data/logs.ron`
+#[derive(Deserialize, Clone, Debug)]
+pub struct LogEntry {
+    pub id: String,
+    pub date: String,
+    pub unlock: UnlockCondition,
+    pub lines: Vec<String>,
+}
+
+impl LogEntry {
+    pub fn display(&self) -> String {
+        let mut lines = vec![format!("{}:", self.date), " ".to_string()];
+        lines.extend(self.lines.iter().cloned());
+        lines.join("\n")
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LogCatalog {
+    pub logs: Vec<LogEntry>,
+}
+
+impl LogCatalog {
+    /// Load the logs from the data folder, falling back to the version embedded at
+    /// compile time (wasm or invalid file).
+    pub fn load() -> Self {
+        match data::read_data_file(LOGS_PATH) {
+            Ok(bytes) => match Self::from_bytes(&bytes) {
+                Ok(catalog) => return catalog,
+                Err(err) => {
+                    error!("[LorePlugin] {}: {}", LOGS_PATH, err);
+                    warn!("[LorePlugin] Using the embedded logs");
+                }
+            },
+            Err(err) => info!(
+                "[LorePlugin] {} not readable ({}), using the embedded logs",
+                LOGS_PATH, err
+            ),
+        }
+
+        Self::from_bytes(include_bytes!("../../data/logs.ron"))
+            .unwrap_or_else(|err| panic!("Invalid embedded logs: {}", err))
+    }
+
+    /// Parse the logs, ids must be unique
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let catalog = from_bytes::<LogCatalog>(bytes).map_err(|err| err.to_string())?;
+
+        let mut ids = HashSet::new();
+        for log in catalog.logs.iter() {
+            if !ids.insert(log.id.as_str()) {
+                return Err(format!("log \"{}\" is defined twice", log.id));
+            }
+        }
+        Ok(catalog)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&LogEntry> {
+        self.logs.iter().find(|log| log.id == id)
+    }
+}
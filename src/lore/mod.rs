@@ -0,0 +1,205 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use ron::de::from_bytes;
+use ron::ser::{to_string_pretty, PrettyConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cheat_codes::CheatCodeResource,
+    console::{
+        AddConsoleCommand, ArgKind, ConsoleCommand, ConsoleCommandEvent, PrintToConsoleEvent,
+    },
+    data,
+    states::GameStates,
+    stats::GameStatsResource,
+    toast::ShowToast,
+};
+
+use self::catalog::{LogCatalog, LogEntry, UnlockCondition};
+
+mod catalog;
+
+const LORE_FILE: &str = "lore.ron";
+
+pub struct LorePlugin;
+
+impl Plugin for LorePlugin {
+    fn build(&self, app: &mut App) {
+        let catalog = LogCatalog::load();
+        info!("[LorePlugin] {} logs loaded", catalog.logs.len());
+        app.insert_resource(catalog);
+        app.insert_resource(LoreResource::load(data::user_data_path().join(LORE_FILE)));
+
+        app.add_console_command(ConsoleCommand::new("logs", "list the unlocked log entries"))
+            .add_console_command(
+                ConsoleCommand::new("log", "display a log entry").with_arg("id", ArgKind::Text),
+            );
+        app.add_system(log_commands);
+        app.add_system(unlock_logs);
+        app.add_system_set(
+            SystemSet::on_enter(GameStates::Console).with_system(count_terminal_visit),
+        );
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct LoreFile {
+    unlocked: Vec<String>,
+    terminals_visited: usize,
+}
+
+/// Logs unlocked by the player, kept across runs
+pub struct LoreResource {
+    pub path: PathBuf,
+    pub unlocked: BTreeSet<String>,
+    pub terminals_visited: usize,
+}
+
+impl LoreResource {
+    /// Load the progression saved at `path`, starting from scratch if the file
+    /// doesn't exist yet or can't be read
+    pub fn load(path: PathBuf) -> Self {
+        let file = match std::fs::read(&path) {
+            Ok(bytes) => match from_bytes::<LoreFile>(&bytes) {
+                Ok(file) => file,
+                Err(err) => {
+                    error!("[LorePlugin] Unable to parse {}: {}", path.display(), err);
+                    LoreFile::default()
+                }
+            },
+            Err(_) => {
+                info!("[LorePlugin] No lore saved in {}", path.display());
+                LoreFile::default()
+            }
+        };
+
+        Self {
+            path,
+            unlocked: file.unlocked.into_iter().collect(),
+            terminals_visited: file.terminals_visited,
+        }
+    }
+
+    pub fn save(&self) {
+        let file = LoreFile {
+            unlocked: self.unlocked.iter().cloned().collect(),
+            terminals_visited: self.terminals_visited,
+        };
+
+        let result = to_string_pretty(&file, PrettyConfig::new())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                data::write_user_file(&self.path, &contents).map_err(|err| err.to_string())
+            });
+
+        if let Err(err) = result {
+            error!(
+                "[LorePlugin] Unable to save the lore in {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+
+    pub fn is_unlocked(&self, log: &LogEntry) -> bool {
+        matches!(log.unlock, UnlockCondition::Always) || self.unlocked.contains(&log.id)
+    }
+}
+
+fn count_terminal_visit(mut lore: ResMut<LoreResource>) {
+    lore.terminals_visited += 1;
+    lore.save();
+}
+
+/// Unlock the logs whose condition is met, the player is told where to read them
+fn unlock_logs(
+    mut lore: ResMut<LoreResource>,
+    catalog: Res<LogCatalog>,
+    stats_res: Res<GameStatsResource>,
+    cheat_codes: Res<CheatCodeResource>,
+    game_state: Res<State<GameStates>>,
+    mut toast_writer: EventWriter<ShowToast>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+) {
+    let newly_unlocked: Vec<&LogEntry> = catalog
+        .logs
+        .iter()
+        .filter(|log| !lore.is_unlocked(log))
+        .filter(|log| match log.unlock {
+            UnlockCondition::Always => true,
+            UnlockCondition::Distance(distance) => stats_res.distance >= distance,
+            UnlockCondition::CheatActivated(kind) => cheat_codes.is_code_activated(&kind),
+            UnlockCondition::TerminalsVisited(count) => lore.terminals_visited >= count,
+        })
+        .collect();
+
+    if newly_unlocked.is_empty() {
+        return;
+    }
+
+    for log in newly_unlocked.iter() {
+        info!("[LorePlugin] Log \"{}\" unlocked", log.id);
+        lore.unlocked.insert(log.id.clone());
+
+        if *game_state.current() == GameStates::Console {
+            print_to_console.send(PrintToConsoleEvent(format!(
+                "New log unlocked, enter \"log {}\" to read it.",
+                log.id
+            )));
+        } else {
+            toast_writer.send(ShowToast {
+                value: "New log unlocked, read it in a terminal".to_string(),
+                duration: Duration::from_secs(3),
+            });
+        }
+    }
+    lore.save();
+}
+
+fn log_commands(
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    lore: Res<LoreResource>,
+    catalog: Res<LogCatalog>,
+) {
+    for event in command_reader.iter() {
+        match event.name.as_str() {
+            "logs" => {
+                let unlocked: Vec<&LogEntry> = catalog
+                    .logs
+                    .iter()
+                    .filter(|log| lore.is_unlocked(log))
+                    .collect();
+
+                let mut lines = vec![format!(
+                    "Unlocked logs: {}/{}",
+                    unlocked.len(),
+                    catalog.logs.len()
+                )];
+                lines.extend(
+                    unlocked
+                        .iter()
+                        .map(|log| format!("    - {} ({})", log.id, log.date)),
+                );
+                lines.push("Enter \"log <id>\" to read one.".to_string());
+                print_to_console.send(PrintToConsoleEvent(lines.join("\n")));
+            }
+            "log" => {
+                let id = event.text(0).unwrap_or_default();
+                let message = match catalog.get(id) {
+                    Some(log) if lore.is_unlocked(log) => log.display(),
+                    Some(_) => format!("Log \"{}\" is still locked.", id),
+                    None => format!(
+                        "Log \"{}\" not found.\nEnter \"logs\" to list the unlocked logs.",
+                        id
+                    ),
+                };
+                print_to_console.send(PrintToConsoleEvent(message));
+            }
+            _ => {}
+        }
+    }
+}
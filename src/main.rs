@@ -1,38 +1,38 @@
 use std::time::Duration;
 
+#[cfg(debug_assertions)]
+use bevy::asset::AssetServerSettings;
 use bevy::prelude::*;
 #[cfg(debug_assertions)]
 use bevy_inspector_egui::WorldInspectorPlugin;
 use bevy_kira_audio::AudioPlugin;
 use bevy_rapier2d::prelude::*;
 
+#[cfg(feature = "dev-console")]
 use cheat_codes::CheatCodeResource;
+#[cfg(debug_assertions)]
+use cheaters_never_win::chunk_editor;
+use cheaters_never_win::{
+    audio, camera, cheat_codes, console, controls, effects, enemies, game_over, high_scores,
+    interactables, letter_gutter, lore, main_menu, pause_menu, physics, platforms, run, runner,
+    states, stats, tab_menu, toast,
+};
+use controls::{Action, ActionInput};
 use toast::ShowToast;
 
-mod audio;
-mod camera;
-mod cheat_codes;
-mod console;
-mod effects;
-mod enemies;
-mod game_over;
-mod interactables;
-mod letter_gutter;
-mod main_menu;
-mod pause_menu;
-mod physics;
-mod platforms;
-mod runner;
-mod states;
-mod stats;
-mod tab_menu;
-mod toast;
-
 fn main() {
     let mut app = App::new();
 
+    // reload the assets (e.g. the chunks) when they change on disk
+    #[cfg(debug_assertions)]
+    app.insert_resource(AssetServerSettings {
+        watch_for_changes: true,
+        ..Default::default()
+    });
     #[cfg(debug_assertions)]
     app.add_plugin(WorldInspectorPlugin::new());
+    #[cfg(debug_assertions)]
+    app.add_plugin(chunk_editor::ChunkEditorPlugin);
 
     app.insert_resource(WindowDescriptor {
         resizable: false,
@@ -41,9 +41,12 @@ fn main() {
         title: "Bevy Jam #1".to_string(),
         ..Default::default()
     })
-    .insert_resource(cheat_codes::CheatCodeResource::new())
     .add_plugin(main_menu::MainMenuPlugin)
     .add_plugins(DefaultPlugins)
+    // added after the default plugins so the catalog loading can be logged
+    .add_plugin(run::RunPlugin)
+    .add_plugin(cheat_codes::CheatCodesPlugin)
+    .add_plugin(controls::ControlsPlugin)
     .add_plugin(tab_menu::TabMenuPlugin)
     .add_plugin(console::ConsolePlugin)
     .add_plugin(runner::RunnerPlugin)
@@ -54,37 +57,46 @@ fn main() {
     .add_plugin(enemies::EnemiesPlugin)
     .add_plugin(toast::ToastPlugin)
     .add_plugin(game_over::GameOverPlugin)
+    .add_plugin(high_scores::HighScoresPlugin)
     .add_plugin(interactables::InteractablesPlugin)
     .add_plugin(letter_gutter::LetterGutterPlugin)
     .add_plugin(AudioPlugin)
     .add_state(states::GameStates::MainMenu)
     .add_plugin(stats::GameStatsPlugin)
+    .add_plugin(lore::LorePlugin)
     .add_plugin(effects::EffectsPlugin)
     .add_plugin(audio::GameAudioPlugin)
     .add_startup_system(camera::add_camera)
-    // TODO: remove
-    .add_startup_system(test_codes)
-    .add_system_set(SystemSet::on_enter(states::GameStates::Main).with_system(prelude_text))
-    .run();
+    .add_system_set(SystemSet::on_enter(states::GameStates::Main).with_system(prelude_text));
+
+    #[cfg(feature = "dev-console")]
+    app.add_startup_system(test_codes);
+
+    app.run();
 }
 
+#[cfg(feature = "dev-console")]
 fn test_codes(mut cheat_codes_res: ResMut<CheatCodeResource>) {
     println!(
         "Random text : {}",
-        cheat_codes::generate_random_code(cheat_codes::CheatCodeRarity::Legendary)
+        cheat_codes::generate_random_code(
+            cheat_codes::CheatCodeRarity::Legendary.code_length(),
+            &mut rand::thread_rng(),
+        )
     );
 
-    let next_code = cheat_codes_res.get_next_code();
-    let next_code_code = cheat_codes_res.codes.get(&next_code).unwrap();
-    println!(
-        "Get next cheat code: {:?} with code: {}",
-        next_code, next_code_code.text
-    );
+    if let Some(next_code) = cheat_codes_res.get_next_code(&mut rand::thread_rng()) {
+        let next_code_code = cheat_codes_res.codes.get(&next_code).unwrap();
+        println!(
+            "Get next cheat code: {:?} with code: {}",
+            next_code, next_code_code.text
+        );
 
-    println!(
-        "Is code activated: {}",
-        cheat_codes_res.is_code_activated(&next_code)
-    );
+        println!(
+            "Is code activated: {}",
+            cheat_codes_res.is_code_activated(&next_code)
+        );
+    }
 
     let result = cheat_codes_res.activate_code("jump");
     println!("Trying to activate code : {:?}", &result);
@@ -94,14 +106,20 @@ fn test_codes(mut cheat_codes_res: ResMut<CheatCodeResource>) {
     }
 }
 
-fn prelude_text(mut toasts: EventWriter<ShowToast>) {
+fn prelude_text(mut toasts: EventWriter<ShowToast>, actions: ActionInput) {
     // empty to avoid issues
     toasts.send(ShowToast {
-        value: "Press 'D' to move forward".to_string(),
+        value: format!(
+            "Press {} to move forward",
+            actions.prompt_name(Action::MoveRight)
+        ),
         duration: Duration::from_secs(3),
     });
     toasts.send(ShowToast {
-        value: "Press TAB to open journal".to_string(),
+        value: format!(
+            "Press {} to open journal",
+            actions.prompt_name(Action::TabMenu)
+        ),
         duration: Duration::from_secs(3),
     });
     toasts.send(ShowToast {
@@ -109,7 +127,10 @@ fn prelude_text(mut toasts: EventWriter<ShowToast>) {
         duration: Duration::from_secs(3),
     });
     toasts.send(ShowToast {
-        value: "Press `E` to interact with terminal".to_string(),
+        value: format!(
+            "Press {} to interact with terminal",
+            actions.prompt_name(Action::Interact)
+        ),
         duration: Duration::from_secs(3),
     });
     toasts.send(ShowToast {
@@ -3,6 +3,7 @@ use bevy::{app::AppExit, prelude::*};
 use crate::{
     audio::{GameAudioOptions, GameAudioState},
     pause_menu::button::{UIButton, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON},
+    run::NewRunEvent,
     states::GameStates,
 };
 mod ui;
@@ -22,8 +23,15 @@ impl Plugin for MainMenuPlugin {
                 .with_system(ui::build_ui)
                 .with_system(spawn_main_menu_background),
         );
+        app.insert_resource(SeedInput {
+            value: String::new(),
+        });
         // on update
-        app.add_system_set(SystemSet::on_update(GameStates::MainMenu).with_system(button_handler));
+        app.add_system_set(
+            SystemSet::on_update(GameStates::MainMenu)
+                .with_system(button_handler)
+                .with_system(seed_input_handler),
+        );
         // on exit
         app.add_system_set(
             SystemSet::on_exit(GameStates::MainMenu)
@@ -33,6 +41,38 @@ impl Plugin for MainMenuPlugin {
     }
 }
 
+/// Seed typed in the main menu, a random one is used when empty
+pub struct SeedInput {
+    pub value: String,
+}
+
+// u64::MAX has 20 digits, stay below so the value always parses
+const SEED_MAX_DIGITS: usize = 19;
+
+fn seed_input_handler(
+    mut char_evr: EventReader<ReceivedCharacter>,
+    keyboard: Res<Input<KeyCode>>,
+    mut seed_input: ResMut<SeedInput>,
+    mut query: Query<&mut Text, With<ui::SeedText>>,
+) {
+    for ev in char_evr.iter() {
+        if ev.char.is_ascii_digit() && seed_input.value.len() < SEED_MAX_DIGITS {
+            seed_input.value.push(ev.char);
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Back) {
+        seed_input.value.pop();
+    }
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = if seed_input.value.is_empty() {
+            "Seed: random".to_string()
+        } else {
+            format!("Seed: {}", seed_input.value)
+        };
+    }
+}
+
 fn destroy_menu(mut commands: Commands, query: Query<Entity, With<MainMenuEntity>>) {
     info!("[MainMenuPlugin] Destroying state entities before exiting...");
     for entity in query.iter() {
@@ -43,9 +83,11 @@ fn destroy_menu(mut commands: Commands, query: Query<Entity, With<MainMenuEntity
 
 pub fn button_handler(
     mut interaction_query: Query<(&Interaction, &mut UiColor, &UIButton), Changed<Interaction>>,
-    mut game_state: ResMut<State<GameStates>>,
+    mut new_run_writer: EventWriter<NewRunEvent>,
     mut exit: EventWriter<AppExit>,
     mut game_audio_state: ResMut<GameAudioState>,
+    seed_input: Res<SeedInput>,
+    mut game_state: ResMut<State<GameStates>>,
 ) {
     for (interaction, mut color, button) in interaction_query.iter_mut() {
         match *interaction {
@@ -59,7 +101,12 @@ pub fn button_handler(
                 *color = PRESSED_BUTTON.into();
                 match button.name.as_str() {
                     "play" => {
-                        game_state.set(GameStates::Main).unwrap();
+                        new_run_writer.send(NewRunEvent {
+                            seed: seed_input.value.parse::<u64>().ok(),
+                        });
+                    }
+                    "high_scores" => {
+                        game_state.set(GameStates::HighScores).unwrap();
                     }
                     "quit" => {
                         exit.send(AppExit);
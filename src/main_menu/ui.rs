@@ -3,6 +3,9 @@ use bevy_ninepatch::{NinePatchBuilder, NinePatchBundle, NinePatchData};
 
 use crate::pause_menu::button::UIButton;
 
+#[derive(Component)]
+pub struct SeedText;
+
 // building the UI of the console
 pub fn build_ui(
     mut commands: Commands,
@@ -45,7 +48,7 @@ pub fn build_ui(
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
             flex_direction: FlexDirection::ColumnReverse,
-            size: Size::new(Val::Px(500.), Val::Px(400.)),
+            size: Size::new(Val::Px(500.), Val::Px(485.)),
             ..Default::default()
         },
         color: Color::rgba(0., 0., 0., 0.).into(),
@@ -59,7 +62,7 @@ pub fn build_ui(
             margin: Rect::all(Val::Auto),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
-            size: Size::new(Val::Px(500.), Val::Px(400.)),
+            size: Size::new(Val::Px(500.), Val::Px(485.)),
             ..Default::default()
         },
         nine_patch_data: NinePatchData {
@@ -90,7 +93,29 @@ pub fn build_ui(
         ..Default::default()
     };
 
+    // seed of the next run, typed with the number keys
+    let seed_text = TextBundle {
+        text: Text::with_section(
+            "",
+            TextStyle {
+                font: font_handle.clone(),
+                font_size: 28.,
+                color: Color::rgb_u8(205, 205, 205).into(),
+            },
+            TextAlignment {
+                horizontal: HorizontalAlign::Center,
+                ..Default::default()
+            },
+        ),
+        ..Default::default()
+    };
+
     let play_btn = UIButton::new("Play".to_string(), font_handle.clone(), "play".to_string());
+    let high_scores_btn = UIButton::new(
+        "Scores".to_string(),
+        font_handle.clone(),
+        "high_scores".to_string(),
+    );
     // let options_btn = UIButton::new(
     //     "Options".to_string(),
     //     font_handle.clone(),
@@ -106,7 +131,9 @@ pub fn build_ui(
             parent.spawn_bundle(background);
             parent.spawn_bundle(container).with_children(|parent| {
                 parent.spawn_bundle(pause_title);
+                parent.spawn_bundle(seed_text).insert(SeedText);
                 play_btn.spawn(parent);
+                high_scores_btn.spawn(parent);
                 // TODO: Add options back
                 // options_btn.spawn(parent);
                 quit_btn.spawn(parent);
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::controls::ActionInput;
+
 pub const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
 pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
 pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
@@ -77,3 +79,90 @@ pub fn button_system(
         }
     }
 }
+
+/// Button selected with the arrow keys or the gamepad
+pub struct ButtonFocus {
+    pub entity: Option<Entity>,
+}
+
+impl ButtonFocus {
+    pub fn new() -> Self {
+        Self { entity: None }
+    }
+}
+
+/// Move the focus between the buttons on screen, top to bottom and left to right,
+/// and click the focused one on confirm
+pub fn navigate_buttons(
+    mut actions: ActionInput,
+    mut focus: ResMut<ButtonFocus>,
+    mut button_query: Query<(Entity, &GlobalTransform, &mut Interaction), With<UIButton>>,
+) {
+    let mut buttons: Vec<(Entity, Vec3)> = button_query
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation))
+        .collect();
+
+    // the menu holding the focused button was closed
+    if let Some(entity) = focus.entity {
+        if !buttons.iter().any(|(button, _)| *button == entity) {
+            focus.entity = None;
+        }
+    }
+    if buttons.is_empty() {
+        return;
+    }
+
+    // ui y grows upward
+    buttons.sort_by(|(_, a), (_, b)| {
+        b.y.round()
+            .total_cmp(&a.y.round())
+            .then(a.x.total_cmp(&b.x))
+    });
+
+    let step = actions.menu_step();
+    let confirm = actions.menu_confirm();
+    if step == 0 && !confirm {
+        return;
+    }
+
+    let current = focus
+        .entity
+        .and_then(|entity| buttons.iter().position(|(button, _)| *button == entity));
+    let index = match current {
+        Some(index) => (index as i32 + step as i32).rem_euclid(buttons.len() as i32) as usize,
+        // first input only focuses the first button
+        None => {
+            focus.entity = Some(buttons[0].0);
+            return;
+        }
+    };
+    focus.entity = Some(buttons[index].0);
+
+    if confirm && step == 0 {
+        if let Ok((_, _, mut interaction)) = button_query.get_mut(buttons[index].0) {
+            *interaction = Interaction::Clicked;
+        }
+        actions.reset_menu_confirm();
+    }
+}
+
+/// Runs after the menus' button handlers so the focus color isn't overwritten
+pub fn highlight_focused_button(
+    focus: Res<ButtonFocus>,
+    mut button_query: Query<(Entity, &Interaction, &mut UiColor), With<UIButton>>,
+) {
+    for (entity, interaction, mut color) in button_query.iter_mut() {
+        if *interaction != Interaction::None {
+            continue;
+        }
+        let expected: UiColor = if focus.entity == Some(entity) {
+            HOVERED_BUTTON.into()
+        } else {
+            NORMAL_BUTTON.into()
+        };
+        if color.0 != expected.0 {
+            *color = expected;
+        }
+    }
+}
@@ -2,9 +2,10 @@ use bevy::{app::AppExit, prelude::*};
 use bevy_ninepatch::NinePatchPlugin;
 
 use crate::audio::{GameAudioOptions, GameAudioState};
+use crate::controls::{Action, ActionInput};
 use crate::states::GameStates;
 
-use self::button::{UIButton, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON};
+use self::button::{ButtonFocus, UIButton, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON};
 
 pub mod button;
 mod ui;
@@ -17,6 +18,11 @@ impl Plugin for PauseMenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(NinePatchPlugin::<()>::default());
 
+        // keyboard and gamepad navigation of every menu
+        app.insert_resource(ButtonFocus::new());
+        app.add_system(button::navigate_buttons.label("navigate_buttons"));
+        app.add_system_to_stage(CoreStage::PostUpdate, button::highlight_focused_button);
+
         // on enter
         app.add_system_set(SystemSet::on_enter(GameStates::PauseMenu).with_system(ui::build_ui));
         // on update
@@ -26,6 +32,9 @@ impl Plugin for PauseMenuPlugin {
                 .with_system(close_pause_menu)
                 .with_system(button_handler),
         );
+        // hidden while the controls page is open
+        app.add_system_set(SystemSet::on_pause(GameStates::PauseMenu).with_system(destroy_menu));
+        app.add_system_set(SystemSet::on_resume(GameStates::PauseMenu).with_system(ui::build_ui));
         // on exit
         app.add_system_set(SystemSet::on_exit(GameStates::PauseMenu).with_system(destroy_menu));
     }
@@ -39,23 +48,17 @@ fn destroy_menu(mut commands: Commands, query: Query<Entity, With<PauseMenuEntit
     info!("[PauseMenuPlugin] Exiting state");
 }
 
-fn open_pause_menu(
-    mut keyboard: ResMut<Input<KeyCode>>,
-    mut game_state: ResMut<State<GameStates>>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+fn open_pause_menu(mut actions: ActionInput, mut game_state: ResMut<State<GameStates>>) {
+    if actions.just_pressed(Action::Pause) {
         game_state.push(GameStates::PauseMenu).unwrap();
-        keyboard.reset(KeyCode::Escape);
+        actions.reset(Action::Pause);
     }
 }
 
-fn close_pause_menu(
-    mut keyboard: ResMut<Input<KeyCode>>,
-    mut game_state: ResMut<State<GameStates>>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+fn close_pause_menu(mut actions: ActionInput, mut game_state: ResMut<State<GameStates>>) {
+    if actions.just_pressed(Action::Pause) {
         game_state.pop().unwrap();
-        keyboard.reset(KeyCode::Escape);
+        actions.reset(Action::Pause);
     }
 }
 
@@ -79,6 +82,9 @@ pub fn button_handler(
                     "resume" => {
                         game_state.pop().unwrap();
                     }
+                    "controls" => {
+                        game_state.push(GameStates::Controls).unwrap();
+                    }
                     "quit" => {
                         exit.send(AppExit);
                     }
@@ -46,7 +46,7 @@ pub fn build_ui(
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
             flex_direction: FlexDirection::ColumnReverse,
-            size: Size::new(Val::Px(500.), Val::Px(400.)),
+            size: Size::new(Val::Px(500.), Val::Px(485.)),
             ..Default::default()
         },
         color: Color::rgba(0., 0., 0., 0.).into(),
@@ -60,7 +60,7 @@ pub fn build_ui(
             margin: Rect::all(Val::Auto),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
-            size: Size::new(Val::Px(500.), Val::Px(400.)),
+            size: Size::new(Val::Px(500.), Val::Px(485.)),
             ..Default::default()
         },
         nine_patch_data: NinePatchData {
@@ -102,6 +102,11 @@ pub fn build_ui(
         font_handle.clone(),
         "option".to_string(),
     );
+    let controls_btn = UIButton::new(
+        "Controls".to_string(),
+        font_handle.clone(),
+        "controls".to_string(),
+    );
     let quit_btn = UIButton::new("Quit".to_string(), font_handle.clone(), "quit".to_string());
 
     // ---------- UI TREE CONSTRUCTION ----------//
@@ -114,6 +119,7 @@ pub fn build_ui(
                 parent.spawn_bundle(pause_title);
                 resume_btn.spawn(parent);
                 options_btn.spawn(parent);
+                controls_btn.spawn(parent);
                 quit_btn.spawn(parent);
             });
         })
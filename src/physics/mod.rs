@@ -1,7 +1,132 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::states::GameStates;
+use crate::{cheat_codes::CheatCodeKind, states::GameStates};
+
+// world tuning, also used by the chunk linter to know how far the player can jump
+pub const GRAVITY: f32 = 140.0;
+// pixels per physics unit
+pub const PHYSICS_SCALE: f32 = 10.0;
+pub const JUMP_IMPULSE: f32 = 1500.0;
+pub const DASH_IMPULSE: f32 = 1000.0;
+// size of the player's collider, in pixels
+pub const PLAYER_WIDTH: f32 = 30.0;
+pub const PLAYER_HEIGHT: f32 = 70.0;
+// top running speed, in physics units per second divided by the scale
+pub const PLAYER_BASE_SPEED: f32 = 8.0;
+
+/// Running speed and how fast it's reached, see `Player`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpeedTier {
+    pub speed: f32,
+    pub acceleration: f32,
+    pub deceleration: f32,
+}
+
+pub const BASE_SPEED_TIER: SpeedTier = SpeedTier {
+    speed: PLAYER_BASE_SPEED,
+    acceleration: 0.12,
+    deceleration: 0.1,
+};
+
+// fastest first, the best activated boost applies
+const SPEED_TIERS: [(CheatCodeKind, SpeedTier); 3] = [
+    (
+        CheatCodeKind::SpeedBoost3,
+        SpeedTier {
+            speed: 8.9,
+            acceleration: 0.15,
+            deceleration: 0.4,
+        },
+    ),
+    (
+        CheatCodeKind::SpeedBoost2,
+        SpeedTier {
+            speed: 8.6,
+            acceleration: 0.14,
+            deceleration: 0.3,
+        },
+    ),
+    (
+        CheatCodeKind::SpeedBoost1,
+        SpeedTier {
+            speed: 8.3,
+            acceleration: 0.13,
+            deceleration: 0.2,
+        },
+    ),
+];
+
+pub fn speed_tier(cheats: &[CheatCodeKind]) -> SpeedTier {
+    SPEED_TIERS
+        .iter()
+        .find(|(kind, _)| cheats.contains(kind))
+        .map_or(BASE_SPEED_TIER, |(_, tier)| *tier)
+}
+
+/// How far the player can move with the activated cheats. Jump is a cheat code too,
+/// the player can only walk off the platforms until it's activated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovementProfile {
+    pub speed: f32,
+    pub jump: bool,
+    pub double_jump: bool,
+    pub dash: bool,
+    pub fly: bool,
+    pub move_left: bool,
+}
+
+impl MovementProfile {
+    pub fn from_cheats(cheats: &[CheatCodeKind]) -> Self {
+        Self {
+            speed: speed_tier(cheats).speed,
+            jump: cheats.contains(&CheatCodeKind::Jump),
+            double_jump: cheats.contains(&CheatCodeKind::DoubleJump),
+            dash: cheats.contains(&CheatCodeKind::Dash),
+            fly: cheats.contains(&CheatCodeKind::Fly),
+            move_left: cheats.contains(&CheatCodeKind::MoveLeft),
+        }
+    }
+
+    /// Highest climb from a platform top to the next one, in pixels
+    pub fn max_rise(&self) -> f32 {
+        if !self.jump {
+            0.0
+        } else if self.double_jump {
+            2.0 * jump_height()
+        } else {
+            jump_height()
+        }
+    }
+
+    /// Widest gap the player can jump over landing at the height they started from,
+    /// in pixels, without flying
+    pub fn jump_reach(&self) -> f32 {
+        if !self.jump {
+            return 0.0;
+        }
+        let mut reach = jump_distance(self.speed);
+        if self.double_jump {
+            reach *= 2.0;
+        }
+        if self.dash {
+            reach += dash_distance();
+        }
+        reach
+    }
+
+    /// Widest gap the player can jump over landing `rise` pixels higher, only the
+    /// descending half of the jump is left once at that height
+    pub fn reach_with_rise(&self, rise: f32) -> f32 {
+        if rise <= 0.0 {
+            self.jump_reach()
+        } else if rise <= self.max_rise() {
+            self.jump_reach() / 2.0
+        } else {
+            0.0
+        }
+    }
+}
 
 pub struct PhysicsPlugin;
 
@@ -14,8 +139,53 @@ impl Plugin for PhysicsPlugin {
 }
 
 fn setup_physics(mut rapier_config: ResMut<RapierConfiguration>) {
-    rapier_config.gravity = [0.0, -140.0].into();
-    rapier_config.scale = 10.0;
+    rapier_config.gravity = [0.0, -GRAVITY].into();
+    rapier_config.scale = PHYSICS_SCALE;
+}
+
+pub fn player_mass() -> f32 {
+    // the player's collider has the default density of 1
+    (PLAYER_WIDTH / PHYSICS_SCALE) * (PLAYER_HEIGHT / PHYSICS_SCALE)
+}
+
+/// Downward acceleration, in pixels per second squared
+pub fn gravity_acceleration() -> f32 {
+    GRAVITY * PHYSICS_SCALE
+}
+
+/// Horizontal running speed, in pixels per second, for a `Player::speed`
+pub fn run_speed(speed: f32) -> f32 {
+    speed * PHYSICS_SCALE * PHYSICS_SCALE
+}
+
+/// Vertical speed given by a jump, in pixels per second
+pub fn jump_speed() -> f32 {
+    JUMP_IMPULSE / player_mass() * PHYSICS_SCALE
+}
+
+/// Horizontal speed added by a dash, in pixels per second
+pub fn dash_speed() -> f32 {
+    DASH_IMPULSE / player_mass() * PHYSICS_SCALE
+}
+
+fn jump_air_time() -> f32 {
+    2.0 * jump_speed() / gravity_acceleration()
+}
+
+/// Height of a single jump, in pixels
+pub fn jump_height() -> f32 {
+    jump_speed().powi(2) / (2.0 * gravity_acceleration())
+}
+
+/// Horizontal distance of a single jump landing at the height it started from, in
+/// pixels, running at `speed` (see `Player::speed`)
+pub fn jump_distance(speed: f32) -> f32 {
+    run_speed(speed) * jump_air_time()
+}
+
+/// Extra distance of a jump when dashing during the whole jump, in pixels
+pub fn dash_distance() -> f32 {
+    dash_speed() * jump_air_time()
 }
 
 pub fn jump(
@@ -0,0 +1,302 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use ron::de::{from_bytes, from_str};
+
+use super::chunk::{Chunk, ChunksResource};
+use super::{lint, CHUNKS_PATH};
+use crate::cheat_codes::CheatCodeCatalog;
+
+// path of the chunks file for the `AssetServer`, relative to the assets folder
+const CHUNKS_ASSET: &str = "chunks.ron";
+const CHUNK_GROUPS: [&str; 3] = ["prelude_chunks", "basic_chunks", "cheat_chunks"];
+
+/// Content of the chunks file, the parsing error is kept to be reported when the asset
+/// is applied instead of failing the load
+#[derive(TypeUuid)]
+#[uuid = "6f4b1a52-2d8e-4c59-9a61-3e0f7b2c8d14"]
+pub struct ChunksAsset(pub Result<ChunksResource, String>);
+
+#[derive(Default)]
+pub struct ChunksAssetLoader;
+
+impl AssetLoader for ChunksAssetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            load_context.set_default_asset(LoadedAsset::new(ChunksAsset(parse_chunks(bytes))));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+// keeps the chunks asset loaded, and watched in debug builds
+pub struct ChunksHandle(pub Handle<ChunksAsset>);
+
+/// Chunks embedded at compile time, used until the chunks asset is loaded
+pub fn embedded_chunks() -> ChunksResource {
+    parse_chunks(include_bytes!("../../assets/chunks.ron"))
+        .unwrap_or_else(|err| panic!("Invalid embedded chunks: {}", err))
+}
+
+/// Parse the chunks file. The error points at the failing chunk with its group and
+/// index when the chunk is invalid on its own.
+pub fn parse_chunks(bytes: &[u8]) -> Result<ChunksResource, String> {
+    let err = match from_bytes::<ChunksResource>(bytes) {
+        Ok(chunks) => return Ok(chunks),
+        Err(err) => err,
+    };
+
+    // errors raised by serde (unknown variant, missing field) have no position, parse
+    // the chunks one by one to find the culprit
+    let text = std::str::from_utf8(bytes).map_err(|_| err.to_string())?;
+    for span in chunk_spans(text) {
+        if let Err(chunk_err) = from_str::<Chunk>(&text[span.start..span.end]) {
+            let line = if chunk_err.position.line == 0 {
+                span.line
+            } else {
+                span.line + chunk_err.position.line - 1
+            };
+            return Err(format!(
+                "{}[{}] (line {}): {}",
+                span.group, span.index, line, chunk_err.code
+            ));
+        }
+    }
+    Err(err.to_string())
+}
+
+/// Reject chunks the game can't run with: an empty prelude or basic list, or any
+/// problem found by the linter
+pub fn check_chunks(chunks: &ChunksResource, catalog: &CheatCodeCatalog) -> Result<(), String> {
+    if chunks.prelude_chunks.is_empty() {
+        return Err("prelude_chunks is empty".to_string());
+    }
+    if chunks.basic_chunks.is_empty() {
+        return Err("basic_chunks is empty".to_string());
+    }
+
+    let errors = lint::lint_chunks(chunks, catalog)
+        .iter()
+        .map(|err| err.to_string())
+        .collect::<Vec<String>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// Text of a chunk in the chunks file
+struct ChunkSpan {
+    group: &'static str,
+    index: usize,
+    // byte range of the chunk, parentheses included
+    start: usize,
+    end: usize,
+    // line of the opening parenthesis, starting at 1
+    line: usize,
+}
+
+/// Find the chunks of each group by tracking the nesting of the brackets, skipping
+/// strings and comments
+fn chunk_spans(text: &str) -> Vec<ChunkSpan> {
+    let mut spans = Vec::new();
+    let mut depth = 0usize;
+    let mut line = 1;
+    // group being read and index of its next chunk
+    let mut group: Option<(&'static str, usize)> = None;
+    let mut open: Option<(usize, usize)> = None;
+
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        '\n' => line += 1,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                        break;
+                    }
+                }
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                let mut previous = ' ';
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    } else if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ':' if depth == 1 => {
+                let key = text[..i].trim_end();
+                let key = &key[key
+                    .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+                    .len()..];
+                group = CHUNK_GROUPS
+                    .iter()
+                    .find(|name| **name == key)
+                    .map(|name| (*name, 0));
+            }
+            '(' | '[' | '{' => {
+                if c == '(' && depth == 2 && group.is_some() {
+                    open = Some((i, line));
+                }
+                depth += 1;
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                if c == ')' && depth == 2 {
+                    if let (Some((start, start_line)), Some((name, index))) =
+                        (open.take(), group.as_mut())
+                    {
+                        spans.push(ChunkSpan {
+                            group: name,
+                            index: *index,
+                            start,
+                            end: i + 1,
+                            line: start_line,
+                        });
+                        *index += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+pub fn load_chunks_asset(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ChunksHandle(asset_server.load(CHUNKS_ASSET)));
+}
+
+/// Replace the chunks when the file is loaded or modified, a file that doesn't parse or
+/// doesn't pass `check_chunks` is reported and the last valid chunks are kept
+pub fn apply_chunks_asset(
+    mut asset_events: EventReader<AssetEvent<ChunksAsset>>,
+    chunks_assets: Res<Assets<ChunksAsset>>,
+    catalog: Res<CheatCodeCatalog>,
+    mut chunks_resource: ResMut<ChunksResource>,
+) {
+    for event in asset_events.iter() {
+        let handle = match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
+            AssetEvent::Removed { .. } => continue,
+        };
+        let asset = match chunks_assets.get(handle) {
+            Some(asset) => asset,
+            None => continue,
+        };
+
+        let checked = asset
+            .0
+            .as_ref()
+            .map_err(|err| err.clone())
+            .and_then(|chunks| {
+                check_chunks(chunks, &catalog)?;
+                Ok(chunks)
+            });
+        match checked {
+            Ok(chunks) => {
+                chunks_resource.prelude_chunks = chunks.prelude_chunks.clone();
+                chunks_resource.basic_chunks = chunks.basic_chunks.clone();
+                chunks_resource.cheat_chunks = chunks.cheat_chunks.clone();
+                // the indices may not point at the same chunks anymore
+                chunks_resource.last_chunk = None;
+                info!("[PlatformsPlugin] {} loaded", CHUNKS_PATH);
+            }
+            Err(err) => error!(
+                "[PlatformsPlugin] {}: {}, keeping the last valid chunks",
+                CHUNKS_PATH, err
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHUNK: &str = "(
+        platforms: [(platform_kind: Platform1, position: (0.0, -290.0))],
+        enemies: [],
+        next_chunk_offset: 160.0,
+        chunk_offset: 81.0,
+        terminals: [],
+        chars: [],
+    )";
+
+    fn chunks_file(basic_chunks: &[&str]) -> String {
+        format!(
+            "(\n    furthest_x: 0.0,\n    prelude_chunks: [{}],\n    basic_chunks: [{}],\n    cheat_chunks: [],\n)",
+            CHUNK,
+            basic_chunks.join(", ")
+        )
+    }
+
+    #[test]
+    fn valid_chunks() {
+        let chunks = parse_chunks(chunks_file(&[CHUNK, CHUNK]).as_bytes()).unwrap();
+        assert_eq!(chunks.prelude_chunks.len(), 1);
+        assert_eq!(chunks.basic_chunks.len(), 2);
+    }
+
+    #[test]
+    fn error_names_the_chunk() {
+        let broken = CHUNK.replace("Platform1", "Platform42");
+        let err = parse_chunks(chunks_file(&[CHUNK, &broken]).as_bytes()).unwrap_err();
+        assert!(err.starts_with("basic_chunks[1]"), "{}", err);
+        assert!(err.contains("Platform42"), "{}", err);
+    }
+
+    #[test]
+    fn empty_basic_chunks_rejected() {
+        let chunks = parse_chunks(chunks_file(&[]).as_bytes()).unwrap();
+        let catalog =
+            CheatCodeCatalog::from_bytes(include_bytes!("../../data/cheat_codes.ron")).unwrap();
+        assert!(check_chunks(&chunks, &catalog).is_err());
+    }
+
+    #[test]
+    fn spans_skip_strings_and_comments() {
+        let text = "(\n    // prelude_chunks: [(\n    basic_chunks: [\n        (tags: [\"(\"]),\n        /* ) */ (),\n    ],\n)";
+        let spans = chunk_spans(text);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(
+            (spans[0].group, spans[0].index, spans[0].line),
+            ("basic_chunks", 0, 4)
+        );
+        assert_eq!(
+            (spans[1].group, spans[1].index, spans[1].line),
+            ("basic_chunks", 1, 5)
+        );
+        assert_eq!(&text[spans[1].start..spans[1].end], "()");
+    }
+}
@@ -1,51 +1,209 @@
+use std::collections::HashSet;
+
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::distributions::{Alphanumeric, DistString};
 use rand::{seq::SliceRandom, Rng};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::platform;
-use crate::cheat_codes::{randomize_text, CheatCodeKind, CheatCodeRarity, CheatCodeResource};
-use crate::interactables::{spawn_char, spawn_terminal, InteractableComponent};
+use super::seeding::{self, CodeSeedingResource};
+use super::selection;
+use super::synthesis;
+use crate::cheat_codes::{randomize_text, scramble_indices, CheatCodeKind, CheatCodeResource};
+use crate::interactables::{spawn_char, spawn_terminal, CharTextComponent, InteractableComponent};
+use crate::physics::MovementProfile;
+use crate::run::RunSeed;
+use crate::stats::GameStatsResource;
 use crate::{enemies, runner};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct PlatformData {
     pub platform_kind: platform::PlatformKind,
     pub position: Vec2,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct EnemyData {
     pub enemy_kind: enemies::EnemyKind,
     pub position: Vec2,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct CharData {
     pub cheat_kind: Option<CheatCodeKind>,
     pub positions: Vec<Vec2>,
     pub is_random: bool,
 }
 
+// 1 in SYNTHESIZED_CHUNK_ODDS generated chunks is assembled by `synthesis` instead of
+// picked from the handmade ones
+const SYNTHESIZED_CHUNK_ODDS: u32 = 5;
+
 #[derive(Deserialize)]
 pub struct ChunksResource {
     pub prelude_chunks: Vec<Chunk>,
     pub basic_chunks: Vec<Chunk>,
-    pub jump_chunks: Vec<Chunk>,
-    // add chunk vec for each cheat
+    // chunks that need at least one cheat to get past
+    pub cheat_chunks: Vec<Chunk>,
     pub furthest_x: f32,
+    // cheats for which a chunk requiring them was already generated during this run
+    #[serde(skip)]
+    pub introduced_cheats: HashSet<CheatCodeKind>,
+    // last basic or cheat chunk generated, not picked again right after
+    #[serde(skip)]
+    pub last_chunk: Option<ChunkId>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChunkGroup {
+    Basic,
+    Cheat,
+}
+
+/// Group and index of a chunk in `ChunksResource`
+pub type ChunkId = (ChunkGroup, usize);
+
+#[derive(Deserialize, Serialize, Clone)]
 pub struct Chunk {
     pub platforms: Vec<PlatformData>,
     pub enemies: Vec<EnemyData>,
     pub next_chunk_offset: f32,
     pub chunk_offset: f32,
-    // ability dependency? optional?
     pub terminals: Vec<Vec2>,
     pub chars: Vec<CharData>,
+    // the chunk can't be cleared without these cheats activated
+    #[serde(default)]
+    pub required_cheats: Vec<CheatCodeKind>,
+    // the chunk is easier with these cheats, more likely to be picked once activated
+    #[serde(default)]
+    pub recommended_cheats: Vec<CheatCodeKind>,
+    // 0 for the easiest chunks, harder ones show up further in the run
+    #[serde(default)]
+    pub difficulty: u32,
+    // relative chance of being picked among the chunks of the same difficulty
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+    // free-form labels, e.g. the biome
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub min_distance: f32,
+    #[serde(default)]
+    pub max_distance: Option<f32>,
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+// same defaults as the optional fields of the chunks file
+impl Default for Chunk {
+    fn default() -> Self {
+        Self {
+            platforms: Vec::new(),
+            enemies: Vec::new(),
+            next_chunk_offset: 0.0,
+            chunk_offset: 0.0,
+            terminals: Vec::new(),
+            chars: Vec::new(),
+            required_cheats: Vec::new(),
+            recommended_cheats: Vec::new(),
+            difficulty: 0,
+            weight: default_weight(),
+            tags: Vec::new(),
+            min_distance: 0.0,
+            max_distance: None,
+        }
+    }
+}
+
+impl Chunk {
+    pub fn has_required_cheats(&self, cheat_codes: &CheatCodeResource) -> bool {
+        self.required_cheats
+            .iter()
+            .all(|kind| cheat_codes.is_code_activated(kind))
+    }
+
+    fn cheat_weight(&self, cheat_codes: &CheatCodeResource) -> f32 {
+        (1 + self
+            .recommended_cheats
+            .iter()
+            .filter(|kind| cheat_codes.is_code_activated(kind))
+            .count()) as f32
+    }
+}
+
+impl ChunksResource {
+    pub fn get(&self, (group, index): ChunkId) -> &Chunk {
+        match group {
+            ChunkGroup::Basic => &self.basic_chunks[index],
+            ChunkGroup::Cheat => &self.cheat_chunks[index],
+        }
+    }
+
+    /// Pick the next chunk among the ones the player can clear at `distance`. A chunk
+    /// requiring a newly activated cheat is always picked first to introduce the new
+    /// ability, as soon as it's available at `distance`.
+    pub fn choose_chunk(
+        &self,
+        cheat_codes: &CheatCodeResource,
+        distance: f32,
+        rng: &mut impl Rng,
+    ) -> ChunkId {
+        let last_index = |group| match self.last_chunk {
+            Some((last_group, index)) if last_group == group => Some(index),
+            _ => None,
+        };
+
+        let unlocked_chunks = self
+            .cheat_chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| chunk.has_required_cheats(cheat_codes))
+            .collect::<Vec<(usize, &Chunk)>>();
+
+        let new_chunks = unlocked_chunks
+            .iter()
+            .filter(|(_, chunk)| {
+                selection::is_available(chunk, distance)
+                    && chunk
+                        .required_cheats
+                        .iter()
+                        .any(|kind| !self.introduced_cheats.contains(kind))
+            })
+            .collect::<Vec<&(usize, &Chunk)>>();
+
+        if let Some((index, _)) = new_chunks.choose(rng) {
+            return (ChunkGroup::Cheat, *index);
+        }
+
+        // roll for "cheat chunk" (chunk that requires an acquired cheat to get past)
+        if !unlocked_chunks.is_empty() && rng.gen_range(0..=3) == 0 {
+            let candidates = unlocked_chunks
+                .iter()
+                .map(|(_, chunk)| (*chunk, chunk.cheat_weight(cheat_codes)))
+                .collect::<Vec<(&Chunk, f32)>>();
+            let last = unlocked_chunks
+                .iter()
+                .position(|(index, _)| Some(*index) == last_index(ChunkGroup::Cheat));
+
+            if let Some(i) = selection::select_chunk(&candidates, distance, last, rng) {
+                return (ChunkGroup::Cheat, unlocked_chunks[i].0);
+            }
+        }
+
+        let candidates = self
+            .basic_chunks
+            .iter()
+            .map(|chunk| (chunk, 1.0))
+            .collect::<Vec<(&Chunk, f32)>>();
+        let index =
+            selection::select_chunk(&candidates, distance, last_index(ChunkGroup::Basic), rng)
+                // no basic chunk available that far, fall back to any of them
+                .unwrap_or_else(|| rng.gen_range(0..self.basic_chunks.len()));
+        (ChunkGroup::Basic, index)
+    }
 }
 
 pub fn spawn_chunk(
@@ -56,6 +214,9 @@ pub fn spawn_chunk(
     asset_server: &AssetServer,
     texture_atlases: &mut Assets<TextureAtlas>,
     cheat_codes: &CheatCodeResource,
+    // letters that must appear, placed in the random char slots of the chunk
+    seeded_chars: &mut Vec<char>,
+    rng: &mut impl Rng,
 ) {
     for platform_data in chunk.platforms.iter() {
         platform::spawn_platform(
@@ -88,46 +249,61 @@ pub fn spawn_chunk(
 
     // TODO: needs some refactoring
     for ch_data in &chunk.chars {
-        if let Some(cheat_kind) = ch_data.cheat_kind {
-            let code = cheat_codes.codes.get(&cheat_kind).unwrap();
-            let shuffled_text = match code.rarity {
-                CheatCodeRarity::Mandatory => {
-                    randomize_text(&code.text, vec![2, 3, 1, 0], ch_data.is_random)
-                }
-                CheatCodeRarity::Common => {
-                    randomize_text(&code.text, vec![2, 3, 1, 0], ch_data.is_random)
-                }
-                CheatCodeRarity::Rare => {
-                    randomize_text(&code.text, vec![2, 5, 3, 1, 0, 4], ch_data.is_random)
-                }
-                CheatCodeRarity::Legendary => {
-                    randomize_text(&code.text, vec![4, 2, 6, 3, 1, 7, 0, 5], ch_data.is_random)
-                }
-            };
+        // a code disabled in the catalog has no text, its slots get random letters
+        let code = ch_data.cheat_kind.and_then(|kind| {
+            let code = cheat_codes.codes.get(&kind);
+            if code.is_none() {
+                warn!(
+                    "[PlatformsPlugin] [{:?}] letters in a chunk but the code is disabled",
+                    kind
+                );
+            }
+            code
+        });
 
-            for n in 0..ch_data.positions.len() {
-                let ch_position = ch_data.positions[n].clone();
-                let ch = shuffled_text.chars().nth(n).unwrap();
+        if let Some(code) = code {
+            let shuffled_text = randomize_text(
+                &code.text,
+                scramble_indices(code.text.len(), rng),
+                ch_data.is_random,
+                rng,
+            );
+
+            if ch_data.positions.len() > shuffled_text.len() {
+                warn!(
+                    "[PlatformsPlugin] {} positions for the [{:?}] letters, the code only has {}",
+                    ch_data.positions.len(),
+                    code.kind,
+                    shuffled_text.len()
+                );
+            }
+
+            // the extra positions are left empty
+            for (ch_position, ch) in ch_data.positions.iter().zip(shuffled_text.chars()) {
                 spawn_char(
                     commands,
                     asset_server,
                     texture_atlases,
                     ch,
-                    &(ch_position + Vec2::new(x_offset, 0.0)),
+                    &(*ch_position + Vec2::new(x_offset, 0.0)),
                 );
             }
         } else {
             let rand_chars = Alphanumeric
-                .sample_string(&mut rand::thread_rng(), ch_data.positions.len())
+                .sample_string(rng, ch_data.positions.len())
                 .to_lowercase();
 
             for n in 0..rand_chars.len() {
                 let ch_position = ch_data.positions[n].clone();
+                // the slots left once the seeded letters are placed are decoys
+                let ch = seeded_chars
+                    .pop()
+                    .unwrap_or_else(|| rand_chars.chars().nth(n).unwrap());
                 spawn_char(
                     commands,
                     asset_server,
                     texture_atlases,
-                    rand_chars.chars().nth(n).unwrap(),
+                    ch,
                     &(ch_position + Vec2::new(x_offset, 0.0)),
                 );
             }
@@ -156,6 +332,8 @@ pub fn chunk_test_system(
             &asset_server,
             &mut texture_atlases,
             &cheat_codes,
+            &mut Vec::new(),
+            &mut rand::thread_rng(),
         );
     }
 }
@@ -167,12 +345,11 @@ pub fn generate_prelude_chunk(
     mut chunks_resource: ResMut<ChunksResource>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     cheat_codes: ResMut<CheatCodeResource>,
+    mut run_seed: ResMut<RunSeed>,
 ) {
     if chunks_resource.furthest_x <= 0.0 {
-        let chunk_to_spawn = chunks_resource
-            .prelude_chunks
-            .choose(&mut rand::thread_rng())
-            .unwrap();
+        let rng = &mut run_seed.level_rng;
+        let chunk_to_spawn = chunks_resource.prelude_chunks.choose(rng).unwrap();
 
         spawn_chunk(
             chunk_to_spawn,
@@ -182,6 +359,8 @@ pub fn generate_prelude_chunk(
             &asset_server,
             &mut texture_atlases,
             &cheat_codes,
+            &mut Vec::new(),
+            rng,
         );
         chunks_resource.furthest_x = chunk_to_spawn.next_chunk_offset;
     }
@@ -195,29 +374,52 @@ pub fn generate_chunks(
     player_query: Query<(&runner::Player, &RigidBodyPositionComponent)>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     cheat_codes: ResMut<CheatCodeResource>,
+    mut run_seed: ResMut<RunSeed>,
+    mut code_seeding: ResMut<CodeSeedingResource>,
+    collected_chars: Res<runner::CollectedChars>,
+    char_query: Query<(&CharTextComponent, &Transform)>,
+    stats_res: Res<GameStatsResource>,
 ) {
     assert!(chunks_resource.furthest_x >= 0.0);
+    let RunSeed {
+        codes_rng,
+        level_rng,
+        ..
+    } = &mut *run_seed;
+    let rng = level_rng;
 
     for (_player, rb_pos) in player_query.iter() {
-        if chunks_resource.furthest_x - (rb_pos.position.translation.x * rapier_config.scale)
-            < 2000.0
-        {
+        let player_x = rb_pos.position.translation.x * rapier_config.scale;
+        if chunks_resource.furthest_x - player_x < 2000.0 {
             info!("generating next chunks");
+
+            // letters behind the player are considered lost
+            let mut seeded_chars = match code_seeding.update_target(&cheat_codes, codes_rng) {
+                Some(kind) => seeding::missing_chars(
+                    &cheat_codes.codes[&kind].text,
+                    &collected_chars,
+                    char_query
+                        .iter()
+                        .filter(|(_, transform)| transform.translation.x > player_x)
+                        .map(|(char_component, _)| char_component.value),
+                    rng,
+                ),
+                None => Vec::new(),
+            };
+
+            let profile = MovementProfile::from_cheats(cheat_codes.activated());
             for _ in 0..=4 {
-                // roll for "cheat chunk" (chunk that requires an acquired cheat to get past)
-                let mut cheat_chunk_roll = rand::thread_rng();
-                let chunk_to_spawn = if cheat_chunk_roll.gen_range(0..=3) == 0 {
-                    // TODO: check player's activated cheats
-                    chunks_resource
-                        .jump_chunks
-                        .choose(&mut rand::thread_rng())
-                        .unwrap()
+                let synthesized;
+                let chunk_to_spawn = if rng.gen_range(0..SYNTHESIZED_CHUNK_ODDS) == 0 {
+                    synthesized = synthesis::synthesize_chunk(&profile, rng);
+                    &synthesized
                 } else {
-                    chunks_resource
-                        .basic_chunks
-                        .choose(&mut rand::thread_rng())
-                        .unwrap()
+                    let chunk_id =
+                        chunks_resource.choose_chunk(&cheat_codes, stats_res.distance, rng);
+                    chunks_resource.last_chunk = Some(chunk_id);
+                    chunks_resource.get(chunk_id)
                 };
+                let required_cheats = chunk_to_spawn.required_cheats.clone();
 
                 spawn_chunk(
                     chunk_to_spawn,
@@ -227,14 +429,24 @@ pub fn generate_chunks(
                     &asset_server,
                     &mut texture_atlases,
                     &cheat_codes,
+                    &mut seeded_chars,
+                    rng,
                 );
 
                 chunks_resource.furthest_x += chunk_to_spawn.next_chunk_offset;
+                chunks_resource.introduced_cheats.extend(required_cheats);
             }
         }
     }
 }
 
+/// Start generating from the origin again on a new run
+pub fn reset_chunks(mut chunks_resource: ResMut<ChunksResource>) {
+    chunks_resource.furthest_x = 0.0;
+    chunks_resource.introduced_cheats.clear();
+    chunks_resource.last_chunk = None;
+}
+
 pub fn despawn_platforms(
     mut commands: Commands,
     rapier_config: Res<RapierConfiguration>,
@@ -291,3 +503,30 @@ pub fn despawn_interactables(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::cheat_codes::CheatCodeCatalog;
+
+    #[test]
+    fn required_cheats() {
+        let catalog =
+            CheatCodeCatalog::from_bytes(include_bytes!("../../data/cheat_codes.ron")).unwrap();
+        let mut cheat_codes = CheatCodeResource::new(&catalog, &mut StdRng::seed_from_u64(0));
+        let dash_chunk = Chunk {
+            required_cheats: vec![CheatCodeKind::Jump, CheatCodeKind::Dash],
+            ..Default::default()
+        };
+
+        assert!(Chunk::default().has_required_cheats(&cheat_codes));
+        assert!(!dash_chunk.has_required_cheats(&cheat_codes));
+        for kind in [CheatCodeKind::Jump, CheatCodeKind::Dash] {
+            let text = cheat_codes.codes[&kind].text.clone();
+            cheat_codes.activate_code(&text);
+        }
+        assert!(dash_chunk.has_required_cheats(&cheat_codes));
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt;
+
+use super::chunk::{Chunk, ChunksResource};
+use super::reachability;
+use crate::cheat_codes::{CheatCodeCatalog, CheatCodeKind};
+use crate::physics::{self, MovementProfile};
+
+// gaps narrower than the player are walked over
+const WALKABLE_GAP: f32 = physics::PLAYER_WIDTH;
+// touching platforms share an edge, they only overlap past this depth
+const OVERLAP_TOLERANCE: f32 = 0.01;
+
+#[derive(Debug, PartialEq)]
+pub enum ChunkProblem {
+    // the code can't be spawned, `spawn_chunk` panics
+    UnknownCheatCode(CheatCodeKind),
+    TooManyCharPositions {
+        kind: CheatCodeKind,
+        positions: usize,
+        code_length: usize,
+    },
+    OverlappingPlatforms(usize, usize),
+    NonPositiveNextChunkOffset(f32),
+    GapTooWide {
+        from_x: f32,
+        to_x: f32,
+        reach: f32,
+    },
+    GapToNextChunk {
+        gap: f32,
+        reach: f32,
+    },
+    // found by `reachability`, the gaps are fine but a climb or a wall is in the way
+    ExitUnreachable,
+}
+
+/// A problem found in a chunk of `assets/chunks.ron`
+#[derive(Debug, PartialEq)]
+pub struct ChunkLintError {
+    // list of the chunk in the file, e.g. "basic_chunks"
+    pub group: &'static str,
+    pub index: usize,
+    pub problem: ChunkProblem,
+}
+
+impl fmt::Display for ChunkLintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: ", self.group, self.index)?;
+        match &self.problem {
+            ChunkProblem::UnknownCheatCode(kind) => {
+                write!(f, "[{:?}] letters but no such enabled cheat code", kind)
+            }
+            ChunkProblem::TooManyCharPositions {
+                kind,
+                positions,
+                code_length,
+            } => write!(
+                f,
+                "{} positions for the [{:?}] letters, the code only has {}",
+                positions, kind, code_length
+            ),
+            ChunkProblem::OverlappingPlatforms(a, b) => {
+                write!(f, "platforms {} and {} overlap", a, b)
+            }
+            ChunkProblem::NonPositiveNextChunkOffset(offset) => {
+                write!(f, "next_chunk_offset must be positive, got {}", offset)
+            }
+            ChunkProblem::GapTooWide {
+                from_x,
+                to_x,
+                reach,
+            } => write!(
+                f,
+                "gap from x = {} to x = {} is wider than the {:.0} pixels the player can jump",
+                from_x, to_x, reach
+            ),
+            ChunkProblem::GapToNextChunk { gap, reach } => write!(
+                f,
+                "gap of {:.0} pixels to the next chunk, the player can jump {:.0}",
+                gap, reach
+            ),
+            ChunkProblem::ExitUnreachable => write!(
+                f,
+                "the last platform can't be reached with the required cheats"
+            ),
+        }
+    }
+}
+
+/// Widest gap the player can jump over with the given cheats, in pixels, `None` when
+/// flying. Jump must be in the cheats, the player can't jump before it's activated.
+pub fn jump_reach(cheats: &[CheatCodeKind]) -> Option<f32> {
+    let profile = MovementProfile::from_cheats(cheats);
+    if profile.fly {
+        None
+    } else {
+        Some(profile.jump_reach())
+    }
+}
+
+/// Left, right, bottom and top edges of the platforms of a chunk
+pub fn platform_bounds(chunk: &Chunk) -> Vec<(f32, f32, f32, f32)> {
+    chunk
+        .platforms
+        .iter()
+        .map(|platform| {
+            let half_size = platform.platform_kind.size() / 2.0;
+            (
+                platform.position.x - half_size.x,
+                platform.position.x + half_size.x,
+                platform.position.y - half_size.y,
+                platform.position.y + half_size.y,
+            )
+        })
+        .collect()
+}
+
+/// Horizontal span of the platforms, relative to where the chunk is placed
+fn chunk_span(chunk: &Chunk) -> Option<(f32, f32)> {
+    let bounds = platform_bounds(chunk);
+    if bounds.is_empty() {
+        return None;
+    }
+
+    let left = bounds.iter().map(|b| b.0).fold(f32::INFINITY, f32::min);
+    let right = bounds.iter().map(|b| b.1).fold(f32::NEG_INFINITY, f32::max);
+    Some((chunk.chunk_offset + left, chunk.chunk_offset + right))
+}
+
+fn lint_chunk(chunk: &Chunk, catalog: &CheatCodeCatalog) -> Vec<ChunkProblem> {
+    let mut problems = Vec::new();
+
+    for char_data in chunk.chars.iter() {
+        let kind = match char_data.cheat_kind {
+            Some(kind) => kind,
+            // random letters, any count works
+            None => continue,
+        };
+        match catalog.get(&kind) {
+            Some(code) if char_data.positions.len() > code.code_length() => {
+                problems.push(ChunkProblem::TooManyCharPositions {
+                    kind,
+                    positions: char_data.positions.len(),
+                    code_length: code.code_length(),
+                })
+            }
+            Some(_) => {}
+            None => problems.push(ChunkProblem::UnknownCheatCode(kind)),
+        }
+    }
+
+    let bounds = platform_bounds(chunk);
+    for (i, a) in bounds.iter().enumerate() {
+        for (j, b) in bounds.iter().enumerate().skip(i + 1) {
+            if a.0 < b.1 - OVERLAP_TOLERANCE
+                && b.0 < a.1 - OVERLAP_TOLERANCE
+                && a.2 < b.3 - OVERLAP_TOLERANCE
+                && b.2 < a.3 - OVERLAP_TOLERANCE
+            {
+                problems.push(ChunkProblem::OverlappingPlatforms(i, j));
+            }
+        }
+    }
+
+    if chunk.next_chunk_offset <= 0.0 {
+        problems.push(ChunkProblem::NonPositiveNextChunkOffset(
+            chunk.next_chunk_offset,
+        ));
+    }
+
+    // sweep from left to right, any x not covered by a platform must be jumped over
+    let problem_count = problems.len();
+    if let Some(reach) = jump_reach(&chunk.required_cheats) {
+        let mut spans: Vec<(f32, f32)> = bounds.iter().map(|b| (b.0, b.1)).collect();
+        spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some(first) = spans.first() {
+            let mut covered = first.1;
+            for (left, right) in spans.iter().skip(1) {
+                let gap = left - covered;
+                if gap > WALKABLE_GAP && gap > reach {
+                    problems.push(ChunkProblem::GapTooWide {
+                        from_x: covered,
+                        to_x: *left,
+                        reach,
+                    });
+                }
+                covered = covered.max(*right);
+            }
+        }
+    }
+
+    // simulate the jumps when no gap is too wide, to not report the same problem twice
+    if problems.len() == problem_count
+        && !reachability::is_clearable(chunk, &MovementProfile::from_cheats(&chunk.required_cheats))
+    {
+        problems.push(ChunkProblem::ExitUnreachable);
+    }
+
+    problems
+}
+
+/// Check every chunk for mistakes making the game panic or a chunk impossible to
+/// clear. Each chunk is checked with its required cheats only, so the basic chunks
+/// must be cleared without jumping. An empty list means the chunks are fine.
+pub fn lint_chunks(chunks: &ChunksResource, catalog: &CheatCodeCatalog) -> Vec<ChunkLintError> {
+    let groups: [(&'static str, &Vec<Chunk>); 3] = [
+        ("prelude_chunks", &chunks.prelude_chunks),
+        ("basic_chunks", &chunks.basic_chunks),
+        ("cheat_chunks", &chunks.cheat_chunks),
+    ];
+
+    // any basic or cheat chunk can follow, the one starting furthest is the worst case
+    let furthest_entry = chunks
+        .basic_chunks
+        .iter()
+        .chain(chunks.cheat_chunks.iter())
+        .filter_map(|chunk| chunk_span(chunk).map(|span| span.0))
+        .fold(f32::NEG_INFINITY, f32::max);
+    // chunks keep being generated before Jump is activated
+    let base_reach = jump_reach(&[]).unwrap_or(f32::INFINITY);
+
+    let mut errors = Vec::new();
+    for (group, group_chunks) in groups {
+        for (index, chunk) in group_chunks.iter().enumerate() {
+            let mut problems = lint_chunk(chunk, catalog);
+
+            if let Some((_, exit)) = chunk_span(chunk) {
+                let gap = chunk.next_chunk_offset + furthest_entry - exit;
+                if gap.is_finite() && gap > WALKABLE_GAP && gap > base_reach {
+                    problems.push(ChunkProblem::GapToNextChunk {
+                        gap,
+                        reach: base_reach,
+                    });
+                }
+            }
+
+            errors.extend(problems.into_iter().map(|problem| ChunkLintError {
+                group,
+                index,
+                problem,
+            }));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platforms::parse_chunks;
+
+    #[test]
+    fn shipped_chunks_are_valid() {
+        let chunks = parse_chunks(include_bytes!("../../assets/chunks.ron")).unwrap();
+        let catalog =
+            CheatCodeCatalog::from_bytes(include_bytes!("../../data/cheat_codes.ron")).unwrap();
+
+        let errors = lint_chunks(&chunks, &catalog)
+            .iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<String>>();
+        assert!(errors.is_empty(), "{}", errors.join("\n"));
+    }
+}
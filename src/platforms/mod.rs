@@ -1,29 +1,47 @@
-use crate::states::GameStates;
+use crate::{run::NewRunStage, states::GameStates};
 use bevy::prelude::*;
-use ron::de::from_bytes;
 pub struct PlatformsPlugin;
 
+mod asset;
 mod chunk;
+pub mod lint;
 pub mod platform;
+pub mod reachability;
+mod seeding;
+pub mod selection;
+pub mod synthesis;
+
+pub use self::asset::{parse_chunks, ChunksAsset, ChunksHandle};
+pub use self::chunk::{
+    CharData, Chunk, ChunkGroup, ChunkId, ChunksResource, EnemyData, PlatformData,
+};
+pub use self::seeding::CodeSeedingResource;
+
+pub const CHUNKS_PATH: &str = "assets/chunks.ron";
 
 impl Plugin for PlatformsPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(
-            from_bytes::<chunk::ChunksResource>(include_bytes!("../../data/chunks.ron")).unwrap(),
-        )
-        .add_system_set(
-            SystemSet::on_enter(GameStates::Main).with_system(
-                chunk::generate_prelude_chunk
-                    .after("setup_physics")
-                    .label("generate_prelude_chunk"),
-            ),
-        )
-        .add_system_set(
-            SystemSet::on_update(GameStates::Main)
-                .with_system(chunk::generate_chunks.after("generate_prelude_chunk"))
-                .with_system(chunk::despawn_platforms.after("generate_prelude_chunk"))
-                .with_system(chunk::despawn_enemies.after("generate_prelude_chunk"))
-                .with_system(chunk::despawn_interactables.after("generate_prelude_chunk")),
-        );
+        app.add_asset::<asset::ChunksAsset>()
+            .init_asset_loader::<asset::ChunksAssetLoader>()
+            .insert_resource(asset::embedded_chunks())
+            .add_startup_system(asset::load_chunks_asset)
+            .add_system(asset::apply_chunks_asset)
+            .insert_resource(seeding::CodeSeedingResource::new())
+            .add_system_to_stage(NewRunStage, chunk::reset_chunks)
+            .add_system_to_stage(NewRunStage, seeding::reset_code_seeding)
+            .add_system_set(
+                SystemSet::on_enter(GameStates::Main).with_system(
+                    chunk::generate_prelude_chunk
+                        .after("setup_physics")
+                        .label("generate_prelude_chunk"),
+                ),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameStates::Main)
+                    .with_system(chunk::generate_chunks.after("generate_prelude_chunk"))
+                    .with_system(chunk::despawn_platforms.after("generate_prelude_chunk"))
+                    .with_system(chunk::despawn_enemies.after("generate_prelude_chunk"))
+                    .with_system(chunk::despawn_interactables.after("generate_prelude_chunk")),
+            );
     }
 }
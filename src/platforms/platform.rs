@@ -1,11 +1,11 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Component)]
 pub struct Platform;
 
-#[derive(Deserialize, Debug, Hash, PartialEq, Eq, Clone)]
+#[derive(Deserialize, Serialize, Debug, Hash, PartialEq, Eq, Clone)]
 pub enum PlatformKind {
     Platform1,
     Platform2,
@@ -15,6 +15,20 @@ pub enum PlatformKind {
     Platform6,
 }
 
+impl PlatformKind {
+    /// Size of the platform's collider, in pixels
+    pub fn size(&self) -> Vec2 {
+        match self {
+            PlatformKind::Platform1 => Vec2::new(158.0, 34.0),
+            PlatformKind::Platform2 => Vec2::new(62.0, 34.0),
+            PlatformKind::Platform3 => Vec2::new(62.0, 34.0),
+            PlatformKind::Platform4 => Vec2::new(83.0, 34.0),
+            PlatformKind::Platform5 => Vec2::new(83.0, 87.0),
+            PlatformKind::Platform6 => Vec2::new(135.0, 87.0),
+        }
+    }
+}
+
 /// Test spawn platform
 #[allow(dead_code)]
 pub fn spawn_test_platforms(
@@ -67,8 +81,9 @@ fn spawn_platform_1(
     rapier_config: &RapierConfiguration,
     asset_server: &AssetServer,
 ) {
-    let collider_size_hx = 158.0 / rapier_config.scale / 2.0;
-    let collider_size_hy = 34.0 / rapier_config.scale / 2.0;
+    let size = PlatformKind::Platform1.size();
+    let collider_size_hx = size.x / rapier_config.scale / 2.0;
+    let collider_size_hy = size.y / rapier_config.scale / 2.0;
 
     commands
         .spawn_bundle(RigidBodyBundle {
@@ -108,8 +123,9 @@ fn spawn_platform_2(
     rapier_config: &RapierConfiguration,
     asset_server: &AssetServer,
 ) {
-    let collider_size_hx = 62.0 / rapier_config.scale / 2.0;
-    let collider_size_hy = 34.0 / rapier_config.scale / 2.0;
+    let size = PlatformKind::Platform2.size();
+    let collider_size_hx = size.x / rapier_config.scale / 2.0;
+    let collider_size_hy = size.y / rapier_config.scale / 2.0;
 
     commands
         .spawn_bundle(RigidBodyBundle {
@@ -149,8 +165,9 @@ fn spawn_platform_3(
     rapier_config: &RapierConfiguration,
     asset_server: &AssetServer,
 ) {
-    let collider_size_hx = 62.0 / rapier_config.scale / 2.0;
-    let collider_size_hy = 34.0 / rapier_config.scale / 2.0;
+    let size = PlatformKind::Platform3.size();
+    let collider_size_hx = size.x / rapier_config.scale / 2.0;
+    let collider_size_hy = size.y / rapier_config.scale / 2.0;
 
     commands
         .spawn_bundle(RigidBodyBundle {
@@ -190,8 +207,9 @@ fn spawn_platform_4(
     rapier_config: &RapierConfiguration,
     asset_server: &AssetServer,
 ) {
-    let collider_size_hx = 83.0 / rapier_config.scale / 2.0;
-    let collider_size_hy = 34.0 / rapier_config.scale / 2.0;
+    let size = PlatformKind::Platform4.size();
+    let collider_size_hx = size.x / rapier_config.scale / 2.0;
+    let collider_size_hy = size.y / rapier_config.scale / 2.0;
 
     commands
         .spawn_bundle(RigidBodyBundle {
@@ -231,8 +249,9 @@ fn spawn_platform_5(
     rapier_config: &RapierConfiguration,
     asset_server: &AssetServer,
 ) {
-    let collider_size_hx = 83.0 / rapier_config.scale / 2.0;
-    let collider_size_hy = 87.0 / rapier_config.scale / 2.0;
+    let size = PlatformKind::Platform5.size();
+    let collider_size_hx = size.x / rapier_config.scale / 2.0;
+    let collider_size_hy = size.y / rapier_config.scale / 2.0;
 
     commands
         .spawn_bundle(RigidBodyBundle {
@@ -272,8 +291,9 @@ fn spawn_platform_6(
     rapier_config: &RapierConfiguration,
     asset_server: &AssetServer,
 ) {
-    let collider_size_hx = 135.0 / rapier_config.scale / 2.0;
-    let collider_size_hy = 87.0 / rapier_config.scale / 2.0;
+    let size = PlatformKind::Platform6.size();
+    let collider_size_hx = size.x / rapier_config.scale / 2.0;
+    let collider_size_hy = size.y / rapier_config.scale / 2.0;
 
     commands
         .spawn_bundle(RigidBodyBundle {
@@ -0,0 +1,310 @@
+use std::collections::VecDeque;
+
+use super::chunk::Chunk;
+use super::lint::platform_bounds;
+use crate::physics::{self, MovementProfile};
+
+// simulation step, the game runs at 60 fps
+const STEP: f32 = 1.0 / 60.0;
+// a jump longer than this is a fall out of the level
+const MAX_STEPS: usize = 5 * 60;
+// touching shapes don't collide below this depth
+const CONTACT_TOLERANCE: f32 = 0.01;
+// fractions of the running speed kept in the air, the player can slow down before a jump
+const AIR_SPEEDS: [f32; 4] = [1.0, 0.5, 0.25, 0.0];
+
+#[derive(Clone, Copy)]
+struct Rect {
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+}
+
+/// One way to leave a platform
+#[derive(Clone, Copy)]
+struct Takeoff {
+    // center of the player
+    x: f32,
+    // horizontal speed, negative to the left
+    vx: f32,
+    jump: bool,
+    double_jump: bool,
+}
+
+/// Follow a takeoff step by step, returns the platform landed on, `None` when the
+/// player hits a wall or a ceiling or falls out of the level
+fn simulate(rects: &[Rect], from: usize, takeoff: Takeoff) -> Option<usize> {
+    let half_width = physics::PLAYER_WIDTH / 2.0;
+    let half_height = physics::PLAYER_HEIGHT / 2.0;
+    let gravity = physics::gravity_acceleration();
+    let lowest = rects.iter().map(|r| r.bottom).fold(f32::INFINITY, f32::min);
+
+    let mut x = takeoff.x;
+    let mut y = rects[from].top + half_height + CONTACT_TOLERANCE;
+    let mut vy = if takeoff.jump {
+        physics::jump_speed()
+    } else {
+        0.0
+    };
+    let mut double_jump = takeoff.double_jump;
+
+    for _ in 0..MAX_STEPS {
+        let previous_feet = y - half_height;
+        vy -= gravity * STEP;
+        // second jump at the top of the first one, like `physics::jump` on a zeroed speed
+        if double_jump && vy <= 0.0 {
+            vy = physics::jump_speed();
+            double_jump = false;
+        }
+        x += takeoff.vx * STEP;
+        y += vy * STEP;
+
+        for (index, rect) in rects.iter().enumerate() {
+            let overlaps = x + half_width > rect.left + CONTACT_TOLERANCE
+                && x - half_width < rect.right - CONTACT_TOLERANCE
+                && y - half_height < rect.top - CONTACT_TOLERANCE
+                && y + half_height > rect.bottom + CONTACT_TOLERANCE;
+            if !overlaps {
+                continue;
+            }
+            return if vy <= 0.0 && previous_feet >= rect.top - CONTACT_TOLERANCE {
+                Some(index)
+            } else {
+                None
+            };
+        }
+
+        if y + half_height < lowest {
+            return None;
+        }
+    }
+    None
+}
+
+/// Every way to leave the platform allowed by the profile: walking off the edges, and
+/// jumps from both edges and the middle at a few air speeds when the player can jump
+fn takeoffs(rect: &Rect, profile: &MovementProfile) -> Vec<Takeoff> {
+    let run_speed = physics::run_speed(profile.speed);
+    let dash_speeds: &[f32] = if profile.dash {
+        &[0.0, physics::dash_speed()]
+    } else {
+        &[0.0]
+    };
+    let double_jumps: &[bool] = if profile.double_jump {
+        &[false, true]
+    } else {
+        &[false]
+    };
+    let directions: &[f32] = if profile.move_left {
+        &[1.0, -1.0]
+    } else {
+        &[1.0]
+    };
+
+    let mut takeoffs = Vec::new();
+    for direction in directions {
+        // the whole body past the edge, the player starts falling right away
+        let edge = if *direction > 0.0 {
+            rect.right + physics::PLAYER_WIDTH / 2.0
+        } else {
+            rect.left - physics::PLAYER_WIDTH / 2.0
+        };
+        for dash_speed in dash_speeds {
+            takeoffs.push(Takeoff {
+                x: edge,
+                vx: direction * (run_speed + dash_speed),
+                jump: false,
+                double_jump: false,
+            });
+        }
+
+        if !profile.jump {
+            continue;
+        }
+        for x in [rect.left, (rect.left + rect.right) / 2.0, rect.right] {
+            for air_speed in AIR_SPEEDS {
+                for dash_speed in dash_speeds {
+                    for double_jump in double_jumps {
+                        takeoffs.push(Takeoff {
+                            x,
+                            vx: direction * (air_speed * run_speed + dash_speed),
+                            jump: true,
+                            double_jump: *double_jump,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    takeoffs
+}
+
+/// Platforms of the chunk the player can get to with the profile, starting from the
+/// leftmost one. Walking along a platform is always considered possible and the
+/// speed can't change mid-air, so this is an approximation of the real movement.
+pub fn reachable_platforms(chunk: &Chunk, profile: &MovementProfile) -> Vec<bool> {
+    let rects: Vec<Rect> = platform_bounds(chunk)
+        .into_iter()
+        .map(|(left, right, bottom, top)| Rect {
+            left,
+            right,
+            bottom,
+            top,
+        })
+        .collect();
+
+    let mut reachable = vec![false; rects.len()];
+    let entry = match leftmost(&rects) {
+        Some(entry) => entry,
+        None => return reachable,
+    };
+    if profile.fly {
+        return vec![true; rects.len()];
+    }
+
+    let mut queue = VecDeque::from([entry]);
+    reachable[entry] = true;
+    while let Some(from) = queue.pop_front() {
+        for takeoff in takeoffs(&rects[from], profile) {
+            if let Some(to) = simulate(&rects, from, takeoff) {
+                if !reachable[to] {
+                    reachable[to] = true;
+                    queue.push_back(to);
+                }
+            }
+        }
+    }
+    reachable
+}
+
+/// Whether the rightmost platform, where the chunk is left, can be reached
+pub fn is_clearable(chunk: &Chunk, profile: &MovementProfile) -> bool {
+    let reachable = reachable_platforms(chunk, profile);
+    let rights = platform_bounds(chunk).into_iter().map(|bounds| bounds.1);
+    match index_of_max(rights) {
+        Some(exit) => reachable[exit],
+        // nothing to cross
+        None => true,
+    }
+}
+
+fn leftmost(rects: &[Rect]) -> Option<usize> {
+    index_of_max(rects.iter().map(|rect| -rect.left))
+}
+
+fn index_of_max(values: impl Iterator<Item = f32>) -> Option<usize> {
+    values
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+
+    use super::*;
+    use crate::cheat_codes::CheatCodeKind;
+    use crate::platforms::{platform::PlatformKind, PlatformData};
+
+    const GROUND_Y: f32 = -290.0;
+
+    fn chunk(platforms: &[(PlatformKind, f32, f32)]) -> Chunk {
+        Chunk {
+            platforms: platforms
+                .iter()
+                .map(|(platform_kind, x, y)| PlatformData {
+                    platform_kind: platform_kind.clone(),
+                    position: Vec2::new(*x, *y),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn profile(cheats: &[CheatCodeKind]) -> MovementProfile {
+        MovementProfile::from_cheats(cheats)
+    }
+
+    #[test]
+    fn flat_chunk() {
+        let flat = chunk(&[
+            (PlatformKind::Platform1, 0.0, GROUND_Y),
+            (PlatformKind::Platform1, 158.0, GROUND_Y),
+            (PlatformKind::Platform1, 316.0, GROUND_Y),
+        ]);
+
+        assert!(is_clearable(&flat, &profile(&[])));
+        assert!(is_clearable(&flat, &profile(&[CheatCodeKind::Jump])));
+    }
+
+    #[test]
+    fn gap_wider_than_jump_reach() {
+        let jump = profile(&[CheatCodeKind::Jump]);
+        let width = PlatformKind::Platform1.size().x;
+        let gap = chunk(&[
+            (PlatformKind::Platform1, 0.0, GROUND_Y),
+            (
+                PlatformKind::Platform1,
+                width + jump.jump_reach() + 50.0,
+                GROUND_Y,
+            ),
+        ]);
+
+        assert!(!is_clearable(&gap, &jump));
+        assert!(is_clearable(
+            &gap,
+            &profile(&[CheatCodeKind::Jump, CheatCodeKind::Fly])
+        ));
+    }
+
+    #[test]
+    fn climb_higher_than_max_rise() {
+        let rise = 250.0;
+        let climb = chunk(&[
+            (PlatformKind::Platform1, 0.0, GROUND_Y),
+            (PlatformKind::Platform1, 250.0, GROUND_Y + rise),
+        ]);
+        let jump = profile(&[CheatCodeKind::Jump]);
+        let double_jump = profile(&[CheatCodeKind::Jump, CheatCodeKind::DoubleJump]);
+        assert!(jump.max_rise() < rise && rise < double_jump.max_rise());
+
+        assert!(!is_clearable(&climb, &jump));
+        assert!(is_clearable(&climb, &double_jump));
+    }
+
+    #[test]
+    fn wall_needs_move_left_or_fly() {
+        // the steps up to the top of the wall go back to the left
+        let wall = chunk(&[
+            (PlatformKind::Platform1, 158.0, GROUND_Y),
+            (PlatformKind::Platform1, 316.0, GROUND_Y),
+            (PlatformKind::Platform1, 474.0, GROUND_Y),
+            (PlatformKind::Platform4, 420.0, -180.0),
+            (PlatformKind::Platform4, 200.0, -70.0),
+            (PlatformKind::Platform4, 420.0, 40.0),
+            (PlatformKind::Platform6, 600.0, -229.5),
+            (PlatformKind::Platform6, 600.0, -142.5),
+            (PlatformKind::Platform6, 600.0, -55.5),
+            (PlatformKind::Platform6, 600.0, 31.5),
+            (PlatformKind::Platform1, 720.0, GROUND_Y),
+        ]);
+
+        for cheats in [
+            vec![CheatCodeKind::Jump],
+            vec![CheatCodeKind::Jump, CheatCodeKind::DoubleJump],
+            vec![CheatCodeKind::Jump, CheatCodeKind::Dash],
+        ] {
+            assert!(!is_clearable(&wall, &profile(&cheats)), "{:?}", cheats);
+        }
+        assert!(is_clearable(
+            &wall,
+            &profile(&[CheatCodeKind::Jump, CheatCodeKind::MoveLeft])
+        ));
+        assert!(is_clearable(
+            &wall,
+            &profile(&[CheatCodeKind::Jump, CheatCodeKind::Fly])
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::cheat_codes::{CheatCodeKind, CheatCodeResource};
+use crate::runner::CollectedChars;
+
+/// Code whose letters are being placed in the generated chunks, so the letters
+/// needed to progress always show up ahead of the player
+pub struct CodeSeedingResource {
+    pub target: Option<CheatCodeKind>,
+}
+
+impl CodeSeedingResource {
+    pub fn new() -> Self {
+        Self { target: None }
+    }
+
+    /// Keep the same target until it is activated, then pick the next one
+    pub fn update_target(
+        &mut self,
+        cheat_codes: &CheatCodeResource,
+        rng: &mut impl Rng,
+    ) -> Option<CheatCodeKind> {
+        let needs_new_target = match self.target {
+            Some(kind) => cheat_codes.is_code_activated(&kind),
+            None => true,
+        };
+
+        if needs_new_target {
+            self.target = cheat_codes.get_next_code(rng);
+            if let Some(kind) = self.target {
+                info!("seeding letters of the [{:?}] cheat code", kind);
+            }
+        }
+
+        self.target
+    }
+}
+
+/// Letters of `code_text` that are neither collected nor waiting in the world,
+/// shuffled so they don't show up in the code order
+pub fn missing_chars(
+    code_text: &str,
+    collected_chars: &CollectedChars,
+    world_chars: impl Iterator<Item = char>,
+    rng: &mut impl Rng,
+) -> Vec<char> {
+    let mut needed: HashMap<char, u32> = HashMap::new();
+    for c in code_text.chars() {
+        *needed.entry(c).or_insert(0) += 1;
+    }
+
+    let mut available = collected_chars.values_map.clone();
+    for c in world_chars {
+        *available.entry(c).or_insert(0) += 1;
+    }
+
+    let mut missing = Vec::new();
+    for (c, count) in needed.into_iter() {
+        let available_count = available.get(&c).copied().unwrap_or(0);
+        for _ in available_count..count {
+            missing.push(c);
+        }
+    }
+
+    // HashMap order is not reproducible, sort before shuffling with the run seed
+    missing.sort_unstable();
+    missing.shuffle(rng);
+    missing
+}
+
+pub fn reset_code_seeding(mut code_seeding: ResMut<CodeSeedingResource>) {
+    *code_seeding = CodeSeedingResource::new();
+}
@@ -0,0 +1,153 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::chunk::Chunk;
+
+// distance the player must cover, as counted by `GameStatsResource`, before harder
+// chunks can be picked
+pub const DIFFICULTY_STEP: f32 = 50.0;
+
+/// Hardest chunk difficulty allowed at `distance`
+pub fn max_difficulty(distance: f32) -> u32 {
+    (distance.max(0.0) / DIFFICULTY_STEP) as u32
+}
+
+/// Whether the chunk can appear at `distance`, following its difficulty and its
+/// distance bounds
+pub fn is_available(chunk: &Chunk, distance: f32) -> bool {
+    chunk.difficulty <= max_difficulty(distance)
+        && distance >= chunk.min_distance
+        && chunk.max_distance.map_or(true, |max| distance <= max)
+}
+
+/// Weight of each candidate for the next pick, the candidates come with a base weight
+/// set by the caller. Chunks close to the hardest allowed difficulty are favored so
+/// the difficulty ramps up with the distance, and `last` (the index of the previous
+/// pick) is excluded to avoid repeating a chunk back to back.
+pub fn selection_weights(
+    candidates: &[(&Chunk, f32)],
+    distance: f32,
+    last: Option<usize>,
+) -> Vec<f32> {
+    let max = max_difficulty(distance);
+
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(index, (chunk, base_weight))| {
+            if Some(index) == last || !is_available(chunk, distance) {
+                return 0.0;
+            }
+            let ramp = 1.0 / (1 + max - chunk.difficulty) as f32;
+            (base_weight * chunk.weight * ramp).max(0.0)
+        })
+        .collect()
+}
+
+/// Index of the next chunk among the candidates, `None` when none is available at
+/// `distance`. The previous pick is only repeated when it's the only one available.
+pub fn select_chunk(
+    candidates: &[(&Chunk, f32)],
+    distance: f32,
+    last: Option<usize>,
+    rng: &mut impl Rng,
+) -> Option<usize> {
+    let indices: Vec<usize> = (0..candidates.len()).collect();
+
+    [last, None].iter().find_map(|excluded| {
+        let weights = selection_weights(candidates, distance, *excluded);
+        indices
+            .choose_weighted(rng, |index| weights[*index])
+            .ok()
+            .copied()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn chunk(difficulty: u32) -> Chunk {
+        Chunk {
+            difficulty,
+            ..Default::default()
+        }
+    }
+
+    fn candidates(chunks: &[Chunk]) -> Vec<(&Chunk, f32)> {
+        chunks.iter().map(|chunk| (chunk, 1.0)).collect()
+    }
+
+    #[test]
+    fn last_chunk_not_repeated() {
+        let chunks = [chunk(0), chunk(0)];
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            assert_eq!(
+                select_chunk(&candidates(&chunks), 0.0, Some(0), &mut rng),
+                Some(1)
+            );
+        }
+    }
+
+    #[test]
+    fn last_chunk_repeated_when_alone() {
+        let chunks = [chunk(0), chunk(1)];
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(
+            select_chunk(&candidates(&chunks), 0.0, Some(0), &mut rng),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn distance_bounds() {
+        let mut bounded = chunk(0);
+        bounded.min_distance = 100.0;
+        bounded.max_distance = Some(200.0);
+
+        assert!(!is_available(&bounded, 50.0));
+        assert!(is_available(&bounded, 100.0));
+        assert!(is_available(&bounded, 200.0));
+        assert!(!is_available(&bounded, 250.0));
+        assert!(!is_available(&chunk(2), DIFFICULTY_STEP));
+        assert!(is_available(&chunk(2), 2.0 * DIFFICULTY_STEP));
+    }
+
+    #[test]
+    fn weights_ramp_up_with_distance() {
+        let chunks = [chunk(0), chunk(1), chunk(2), chunk(3)];
+        let mean_difficulty = |distance: f32| {
+            let weights = selection_weights(&candidates(&chunks), distance, None);
+            let total: f32 = weights.iter().sum();
+            chunks
+                .iter()
+                .zip(weights.iter())
+                .map(|(chunk, weight)| chunk.difficulty as f32 * weight / total)
+                .sum::<f32>()
+        };
+
+        let means = [0.0, 1.0, 2.0, 3.0].map(|steps| mean_difficulty(steps * DIFFICULTY_STEP));
+        assert_eq!(means[0], 0.0);
+        assert!(
+            means.windows(2).all(|pair| pair[0] < pair[1]),
+            "{:?}",
+            means
+        );
+    }
+
+    #[test]
+    fn nothing_available() {
+        let mut far = chunk(0);
+        far.min_distance = 1000.0;
+        let chunks = [far, chunk(1)];
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert_eq!(
+            select_chunk(&candidates(&chunks), 0.0, None, &mut rng),
+            None
+        );
+        assert_eq!(select_chunk(&[], 0.0, None, &mut rng), None);
+    }
+}
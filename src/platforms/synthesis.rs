@@ -0,0 +1,204 @@
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use super::chunk::{CharData, Chunk, PlatformData};
+use super::platform::PlatformKind;
+use super::reachability;
+use crate::physics::MovementProfile;
+
+// center of the ground platforms of the handmade chunks, synthesized chunks start and
+// end at this height so they connect to any of them
+const GROUND_Y: f32 = -290.0;
+// pieces stay between the ground and this height above it, to stay on screen
+const MAX_CLIMB: f32 = 250.0;
+const MAX_DROP: f32 = 200.0;
+// fraction of the physical limits actually used, the player isn't frame perfect
+const SAFETY: f32 = 0.7;
+const MIN_PIECES: usize = 4;
+const MAX_PIECES: usize = 8;
+// letters and terminals float above the platform top, like in `assets/chunks.ron`
+const ITEM_HEIGHT: f32 = 33.0;
+const MAX_CHAR_SLOTS: usize = 2;
+// layouts tried before falling back to flat ground
+const MAX_ATTEMPTS: usize = 10;
+// 1 in TERMINAL_ODDS synthesized chunks has a terminal
+const TERMINAL_ODDS: u32 = 3;
+
+// first and last piece, wide enough to land on when coming from any chunk
+const EDGE_PIECE: PlatformKind = PlatformKind::Platform1;
+const PIECES: [PlatformKind; 6] = [
+    PlatformKind::Platform1,
+    PlatformKind::Platform2,
+    PlatformKind::Platform3,
+    PlatformKind::Platform4,
+    PlatformKind::Platform5,
+    PlatformKind::Platform6,
+];
+
+/// Gap and height rules between two consecutive pieces
+pub struct GapRules {
+    pub max_rise: f32,
+    pub max_drop: f32,
+    reach: f32,
+    reach_rising: f32,
+}
+
+impl GapRules {
+    pub fn from_profile(profile: &MovementProfile) -> Self {
+        // walking off a platform only leads down, and the chunk must end on the ground
+        if !profile.jump {
+            return Self::flat();
+        }
+        let max_rise = profile.max_rise() * SAFETY;
+        Self {
+            max_rise,
+            max_drop: MAX_DROP,
+            reach: profile.jump_reach() * SAFETY,
+            reach_rising: profile.reach_with_rise(max_rise) * SAFETY,
+        }
+    }
+
+    /// No climb, drop nor gap, the pieces end up touching on the ground
+    pub fn flat() -> Self {
+        Self {
+            max_rise: 0.0,
+            max_drop: 0.0,
+            reach: 0.0,
+            reach_rising: 0.0,
+        }
+    }
+
+    /// Widest gap allowed between two pieces whose tops are `rise` pixels apart
+    pub fn max_gap(&self, rise: f32) -> f32 {
+        if rise > self.max_rise {
+            0.0
+        } else if rise > 0.0 {
+            self.reach_rising
+        } else {
+            self.reach
+        }
+    }
+}
+
+/// Assemble a chunk from platform pieces following the rules given by the profile,
+/// layouts the reachability solver can't clear are thrown away
+pub fn synthesize_chunk(profile: &MovementProfile, rng: &mut impl Rng) -> Chunk {
+    let rules = GapRules::from_profile(profile);
+    for _ in 0..MAX_ATTEMPTS {
+        let chunk = assemble_chunk(&rules, rng);
+        if reachability::is_clearable(&chunk, profile) {
+            return chunk;
+        }
+    }
+
+    // flat ground can always be crossed
+    info!("[PlatformsPlugin] No clearable chunk synthesized, falling back to flat ground");
+    assemble_chunk(&GapRules::flat(), rng)
+}
+
+fn assemble_chunk(rules: &GapRules, rng: &mut impl Rng) -> Chunk {
+    let ground_top = GROUND_Y + EDGE_PIECE.size().y / 2.0;
+
+    let mut platforms = vec![PlatformData {
+        platform_kind: EDGE_PIECE,
+        position: Vec2::new(0.0, GROUND_Y),
+    }];
+    let mut right = EDGE_PIECE.size().x / 2.0;
+    let mut top = ground_top;
+
+    let count = rng.gen_range(MIN_PIECES..=MAX_PIECES);
+    for i in 0..count {
+        let (kind, next_top) = if i + 1 == count {
+            // back on the ground, the next chunk starts there
+            (EDGE_PIECE, ground_top)
+        } else {
+            let target = top + rng.gen_range(-rules.max_drop..=rules.max_rise);
+            (
+                PIECES.choose(rng).unwrap().clone(),
+                target.clamp(ground_top, ground_top + MAX_CLIMB),
+            )
+        };
+
+        let gap = rng.gen_range(0.0..=rules.max_gap(next_top - top));
+        let size = kind.size();
+        let position = Vec2::new(right + gap + size.x / 2.0, next_top - size.y / 2.0);
+        right = position.x + size.x / 2.0;
+        top = next_top;
+        platforms.push(PlatformData {
+            platform_kind: kind,
+            position,
+        });
+    }
+
+    let item_positions = platforms
+        .iter()
+        .skip(1)
+        .map(|platform| {
+            let top = platform.position.y + platform.platform_kind.size().y / 2.0;
+            Vec2::new(platform.position.x, top + ITEM_HEIGHT)
+        })
+        .collect::<Vec<Vec2>>();
+    let char_positions = item_positions
+        .choose_multiple(rng, MAX_CHAR_SLOTS)
+        .cloned()
+        .collect::<Vec<Vec2>>();
+    let terminals = if rng.gen_range(0..TERMINAL_ODDS) == 0 {
+        item_positions.choose(rng).cloned().into_iter().collect()
+    } else {
+        Vec::new()
+    };
+
+    // the first piece starts right after the previous chunk, like the handmade ones
+    let chunk_offset = EDGE_PIECE.size().x / 2.0 + 1.0;
+    Chunk {
+        platforms,
+        next_chunk_offset: chunk_offset + right + 1.0,
+        chunk_offset,
+        terminals,
+        chars: vec![CharData {
+            cheat_kind: None,
+            positions: char_positions,
+            is_random: true,
+        }],
+        tags: vec!["synthesized".to_string()],
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::cheat_codes::CheatCodeKind;
+
+    #[test]
+    fn synthesized_chunks_are_clearable() {
+        let profiles = [
+            vec![],
+            vec![CheatCodeKind::Jump],
+            vec![CheatCodeKind::Jump, CheatCodeKind::DoubleJump],
+            vec![
+                CheatCodeKind::Jump,
+                CheatCodeKind::Dash,
+                CheatCodeKind::SpeedBoost1,
+            ],
+            vec![CheatCodeKind::Jump, CheatCodeKind::MoveLeft],
+        ]
+        .map(|cheats| MovementProfile::from_cheats(&cheats));
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for profile in profiles.iter() {
+            for _ in 0..50 {
+                let chunk = synthesize_chunk(profile, &mut rng);
+                let first = chunk.platforms.first().unwrap();
+                let last = chunk.platforms.last().unwrap();
+
+                assert!(reachability::is_clearable(&chunk, profile), "{:?}", profile);
+                assert!((first.position.y - GROUND_Y).abs() < 0.01);
+                assert!((last.position.y - GROUND_Y).abs() < 0.01);
+                assert!(chunk.next_chunk_offset > 0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::ecs::schedule::ShouldRun;
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::states::GameStates;
+
+/// Starts a new run, from the main menu or from the game over screen.
+///
+/// Every plugin owning run-scoped state (resources, world entities) registers a reset
+/// system in [`NewRunStage`], which only runs on frames where this event was sent.
+pub struct NewRunEvent {
+    // random seed if none
+    pub seed: Option<u64>,
+}
+
+/// Seed of the current run and the random generators derived from it.
+///
+/// Each kind of random decision gets its own generator so that, for instance, the
+/// number of footsteps played doesn't change the next generated chunk.
+pub struct RunSeed {
+    pub seed: u64,
+    // cheat code texts and next code to unlock
+    pub codes_rng: StdRng,
+    // chunk choice and letters spawned in chunks
+    pub level_rng: StdRng,
+    // sounds and visuals, no impact on the gameplay
+    pub cosmetic_rng: StdRng,
+}
+
+impl RunSeed {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            codes_rng: StdRng::seed_from_u64(seed),
+            level_rng: StdRng::seed_from_u64(seed ^ 0x9e37_79b9_7f4a_7c15),
+            cosmetic_rng: StdRng::seed_from_u64(seed ^ 0xc2b2_ae3d_27d4_eb4f),
+        }
+    }
+
+    pub fn random() -> Self {
+        Self::new(rand::thread_rng().gen())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, StageLabel)]
+pub struct NewRunStage;
+
+pub struct RunPlugin;
+
+impl Plugin for RunPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NewRunEvent>();
+        app.insert_resource(RunSeed::random());
+        app.add_stage_before(
+            CoreStage::Update,
+            NewRunStage,
+            SystemStage::parallel().with_run_criteria(new_run_requested),
+        );
+        app.add_system_to_stage(NewRunStage, start_new_run);
+        app.add_system_to_stage(NewRunStage, reseed_run.label("reseed_run"));
+    }
+}
+
+fn new_run_requested(mut new_run_reader: EventReader<NewRunEvent>) -> ShouldRun {
+    if new_run_reader.iter().count() > 0 {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+fn reseed_run(mut new_run_reader: EventReader<NewRunEvent>, mut run_seed: ResMut<RunSeed>) {
+    if let Some(event) = new_run_reader.iter().last() {
+        *run_seed = match event.seed {
+            Some(seed) => RunSeed::new(seed),
+            None => RunSeed::random(),
+        };
+        info!("[RunPlugin] Run seed: {}", run_seed.seed);
+    }
+}
+
+fn start_new_run(mut game_state: ResMut<State<GameStates>>) {
+    info!("[RunPlugin] Starting a new run");
+    // replacing the whole stack runs the exit systems of every state (including Main)
+    // so the previous run gets cleaned before the next one is built
+    if let Err(err) = game_state.replace(GameStates::Main) {
+        error!("[RunPlugin] Unable to start a new run: {:?}", err);
+    }
+}
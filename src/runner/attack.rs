@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::Player;
+use crate::audio::{GameAudioOptions, GameAudioState};
+use crate::cheat_codes::{CheatCodeKind, CheatCodeResource};
+use crate::controls::{Action, ActionInput};
+use crate::effects;
+use crate::enemies::Enemy;
+use crate::states::GameStates;
+use crate::stats::EnemyKilledEvent;
+
+const BASE_DAMAGE: f32 = 1.0;
+const BOOSTED_DAMAGE: f32 = 2.0;
+// seconds between two shots
+const BASE_FIRE_RATE: f32 = 0.6;
+const BOOSTED_FIRE_RATE: f32 = 0.3;
+// in physics units
+const PROJECTILE_SPEED: f32 = 120.0;
+const PROJECTILE_LIFETIME: f32 = 1.5;
+
+#[derive(Component)]
+pub struct Projectile {
+    pub damage: f32,
+    pub lifetime: Timer,
+}
+
+pub struct AttackPlugin;
+
+impl Plugin for AttackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(GameStates::Main)
+                .with_system(player_attack)
+                .with_system(projectile_hit_enemy)
+                .with_system(despawn_projectiles),
+        );
+    }
+}
+
+/// Fire a projectile in front of the player when the Attack cheat is activated
+fn player_attack(
+    mut commands: Commands,
+    actions: ActionInput,
+    time: Res<Time>,
+    cheat_codes: Res<CheatCodeResource>,
+    rapier_config: Res<RapierConfiguration>,
+    mut player_query: Query<(&mut Player, &RigidBodyPositionComponent)>,
+) {
+    for (mut player, rb_pos) in player_query.iter_mut() {
+        player.attack_cooldown_timer.tick(time.delta());
+
+        if !cheat_codes.is_code_activated(&CheatCodeKind::Attack)
+            || !actions.just_pressed(Action::Attack)
+            || !player.attack_cooldown_timer.finished()
+        {
+            continue;
+        }
+
+        let fire_rate = if cheat_codes.is_code_activated(&CheatCodeKind::AttackFireRateBoost) {
+            BOOSTED_FIRE_RATE
+        } else {
+            BASE_FIRE_RATE
+        };
+        player
+            .attack_cooldown_timer
+            .set_duration(Duration::from_secs_f32(fire_rate));
+        player.attack_cooldown_timer.reset();
+
+        let damage = if cheat_codes.is_code_activated(&CheatCodeKind::AttackDmgBoost) {
+            BOOSTED_DAMAGE
+        } else {
+            BASE_DAMAGE
+        };
+        let direction = if player.facing_left { -1.0 } else { 1.0 };
+        let position = Vec2::new(
+            rb_pos.position.translation.x + direction * 30.0 / rapier_config.scale,
+            rb_pos.position.translation.y,
+        );
+
+        spawn_projectile(&mut commands, &rapier_config, position, direction, damage);
+    }
+}
+
+fn spawn_projectile(
+    commands: &mut Commands,
+    rapier_config: &RapierConfiguration,
+    position: Vec2,
+    direction: f32,
+    damage: f32,
+) {
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb_u8(102, 255, 102),
+                custom_size: Some(Vec2::new(14.0, 4.0)),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, 101.0)),
+            ..Default::default()
+        })
+        .insert_bundle(RigidBodyBundle {
+            body_type: RigidBodyType::KinematicVelocityBased.into(),
+            position: position.into(),
+            velocity: RigidBodyVelocity {
+                linvel: Vec2::new(direction * PROJECTILE_SPEED, 0.0).into(),
+                angvel: 0.0,
+            }
+            .into(),
+            ..Default::default()
+        })
+        .insert_bundle(ColliderBundle {
+            shape: ColliderShape::cuboid(7.0 / rapier_config.scale, 2.0 / rapier_config.scale)
+                .into(),
+            collider_type: ColliderType::Sensor.into(),
+            flags: ColliderFlags {
+                active_events: ActiveEvents::INTERSECTION_EVENTS,
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        })
+        .insert(ColliderPositionSync::Discrete)
+        .insert(Projectile {
+            damage,
+            lifetime: Timer::from_seconds(PROJECTILE_LIFETIME, false),
+        })
+        .insert(Name::new("Projectile"));
+}
+
+fn projectile_hit_enemy(
+    mut commands: Commands,
+    mut intersection_events: EventReader<IntersectionEvent>,
+    projectile_query: Query<&Projectile>,
+    mut enemy_query: Query<(&mut Enemy, &Transform)>,
+    mut enemy_killed_writer: EventWriter<EnemyKilledEvent>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    mut game_audio_state: ResMut<GameAudioState>,
+) {
+    for event in intersection_events.iter() {
+        if !event.intersecting {
+            continue;
+        }
+
+        let collider1_entity = event.collider1.entity();
+        let collider2_entity = event.collider2.entity();
+
+        // find which collider is the projectile
+        let (projectile_entity, enemy_entity) = if projectile_query.get(collider1_entity).is_ok() {
+            (collider1_entity, collider2_entity)
+        } else if projectile_query.get(collider2_entity).is_ok() {
+            (collider2_entity, collider1_entity)
+        } else {
+            continue;
+        };
+
+        if let Ok((mut enemy, enemy_transform)) = enemy_query.get_mut(enemy_entity) {
+            // already dead, the despawn is pending
+            if enemy.health <= 0.0 {
+                continue;
+            }
+
+            let projectile = projectile_query.get(projectile_entity).unwrap();
+            enemy.health -= projectile.damage;
+            commands.entity(projectile_entity).despawn();
+
+            if enemy.health <= 0.0 {
+                commands.entity(enemy_entity).despawn();
+                effects::spawn_explosion(
+                    enemy_transform.translation.xy(),
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlases,
+                );
+                game_audio_state.queue_sound(
+                    "explosion-sound".to_owned(),
+                    GameAudioOptions {
+                        ..Default::default()
+                    },
+                );
+                enemy_killed_writer.send(EnemyKilledEvent(enemy.score));
+            }
+        }
+    }
+}
+
+fn despawn_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_query: Query<(Entity, &mut Projectile)>,
+) {
+    for (entity, mut projectile) in projectile_query.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+        if projectile.lifetime.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
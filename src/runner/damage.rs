@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::player::{GameOverEvent, PlayerAnimationTimer};
+use super::Player;
+use crate::cheat_codes::{CheatCodeKind, CheatCodeResource};
+use crate::states::GameStates;
+
+// damage multiplier when the Armor cheat is activated
+const ARMOR_MITIGATION: f32 = 0.5;
+// seconds before the shield comes back after absorbing a hit
+pub const SHIELD_RECHARGE_TIME: f32 = 8.0;
+const INVINCIBILITY_TIME: f32 = 3.0;
+// sprite visibility toggles per second while invincible
+const FLASH_FREQUENCY: f32 = 10.0;
+
+/// Every source of damage on the player goes through this event so the defensive
+/// cheats (Shield, Armor, TempInvicibility) are applied in one place
+pub struct PlayerDamageEvent {
+    pub amount: f32,
+}
+
+#[derive(Component)]
+pub struct ShieldBubble;
+
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayerDamageEvent>();
+        app.add_system_set(
+            SystemSet::on_update(GameStates::Main)
+                .with_system(apply_player_damage)
+                .with_system(recharge_shield)
+                .with_system(update_shield_bubble)
+                .with_system(flash_invincible_player),
+        );
+    }
+}
+
+pub fn spawn_shield_bubble(parent: &mut ChildBuilder, asset_server: &AssetServer) {
+    parent
+        .spawn_bundle(SpriteBundle {
+            texture: asset_server.load("cheat_codes/shield.png"),
+            sprite: Sprite {
+                color: Color::rgba(0.6, 0.9, 1.0, 0.5),
+                custom_size: Some(Vec2::new(110.0, 110.0)),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(Vec3::new(0.0, 8.0, 101.0)),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(ShieldBubble);
+}
+
+fn apply_player_damage(
+    mut damage_events: EventReader<PlayerDamageEvent>,
+    mut player_query: Query<&mut Player>,
+    cheat_codes: Res<CheatCodeResource>,
+    mut game_over_event: EventWriter<GameOverEvent>,
+) {
+    for event in damage_events.iter() {
+        for mut player in player_query.iter_mut() {
+            if player.lives <= 0 || !player.invincibility_timer.finished() || player.god_mode {
+                continue;
+            }
+
+            // the shield absorbs the whole hit
+            if cheat_codes.is_code_activated(&CheatCodeKind::Shield) && player.has_shield {
+                player.has_shield = false;
+                player.shield_recharge_timer.reset();
+                info!("Shield absorbed {} damage", event.amount);
+                continue;
+            }
+
+            let mut amount = event.amount;
+            if cheat_codes.is_code_activated(&CheatCodeKind::Armor) {
+                amount *= ARMOR_MITIGATION;
+            }
+
+            // a life is lost for every whole point of damage accumulated
+            player.damage_taken += amount;
+            while player.damage_taken >= 1.0 {
+                player.damage_taken -= 1.0;
+                player.lives -= 1;
+            }
+
+            if cheat_codes.is_code_activated(&CheatCodeKind::TempInvicibility) {
+                player
+                    .invincibility_timer
+                    .set_duration(Duration::from_secs_f32(INVINCIBILITY_TIME));
+                player.invincibility_timer.reset();
+            }
+
+            if player.lives <= 0 {
+                game_over_event.send(GameOverEvent);
+            }
+        }
+    }
+}
+
+fn recharge_shield(
+    time: Res<Time>,
+    cheat_codes: Res<CheatCodeResource>,
+    mut player_query: Query<&mut Player>,
+) {
+    if !cheat_codes.is_code_activated(&CheatCodeKind::Shield) {
+        return;
+    }
+
+    for mut player in player_query.iter_mut() {
+        if player.has_shield {
+            continue;
+        }
+
+        player.shield_recharge_timer.tick(time.delta());
+        if player.shield_recharge_timer.finished() {
+            player.has_shield = true;
+        }
+    }
+}
+
+fn update_shield_bubble(
+    cheat_codes: Res<CheatCodeResource>,
+    player_query: Query<&Player>,
+    mut bubble_query: Query<&mut Visibility, With<ShieldBubble>>,
+) {
+    let shielded = cheat_codes.is_code_activated(&CheatCodeKind::Shield)
+        && player_query.iter().any(|player| player.has_shield);
+
+    for mut visibility in bubble_query.iter_mut() {
+        visibility.is_visible = shielded;
+    }
+}
+
+fn flash_invincible_player(
+    time: Res<Time>,
+    mut player_query: Query<&mut Player>,
+    mut sprite_query: Query<&mut Visibility, With<PlayerAnimationTimer>>,
+) {
+    for mut player in player_query.iter_mut() {
+        player.invincibility_timer.tick(time.delta());
+
+        let visible = player.invincibility_timer.finished()
+            || (player.invincibility_timer.elapsed_secs() * FLASH_FREQUENCY) as u32 % 2 == 0;
+
+        for mut visibility in sprite_query.iter_mut() {
+            visibility.is_visible = visible;
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use super::Player;
+use crate::cheat_codes::{CheatCodeKind, CheatCodeResource};
+
+#[derive(Component)]
+pub struct FuelGaugeUIComponent;
+
+#[derive(Component)]
+pub struct FuelGaugeFillComponent;
+
+pub fn build_ui(mut commands: Commands) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(45.),
+                    left: Val::Px(10.),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Px(150.), Val::Px(12.)),
+                display: Display::None,
+                ..Default::default()
+            },
+            color: Color::rgba_u8(0, 0, 0, 160).into(),
+            ..Default::default()
+        })
+        .insert(FuelGaugeUIComponent)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                        ..Default::default()
+                    },
+                    color: Color::rgb_u8(255, 170, 0).into(),
+                    ..Default::default()
+                })
+                .insert(FuelGaugeFillComponent);
+        });
+}
+
+pub fn destroy_ui(mut commands: Commands, query: Query<Entity, With<FuelGaugeUIComponent>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update_gauge(
+    cheat_codes: Res<CheatCodeResource>,
+    player_query: Query<&Player>,
+    mut gauge_query: Query<
+        &mut Style,
+        (With<FuelGaugeUIComponent>, Without<FuelGaugeFillComponent>),
+    >,
+    mut fill_query: Query<&mut Style, With<FuelGaugeFillComponent>>,
+) {
+    // the gauge is only shown once the player can fly
+    let display = if cheat_codes.is_code_activated(&CheatCodeKind::Fly) {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in gauge_query.iter_mut() {
+        style.display = display;
+    }
+
+    if let Some(player) = player_query.iter().last() {
+        for mut style in fill_query.iter_mut() {
+            style.size.width = Val::Percent(player.fuel * 100.);
+        }
+    }
+}
@@ -5,6 +5,9 @@ use super::Player;
 #[derive(Component)]
 pub struct LivesCounterComponent;
 
+#[derive(Component)]
+pub struct LivesCounterUIComponent;
+
 pub fn build_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn_bundle(NodeBundle {
@@ -20,6 +23,7 @@ pub fn build_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
             color: Color::rgba_u8(0, 0, 0, 0).into(),
             ..Default::default()
         })
+        .insert(LivesCounterUIComponent)
         .with_children(|parent| {
             parent
                 .spawn_bundle(TextBundle {
@@ -40,6 +44,12 @@ pub fn build_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
         });
 }
 
+pub fn destroy_ui(mut commands: Commands, query: Query<Entity, With<LivesCounterUIComponent>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 pub fn update_counter(
     mut text_query: Query<&mut Text, With<LivesCounterComponent>>,
     player_query: Query<&Player>,
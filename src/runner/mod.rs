@@ -1,11 +1,19 @@
 use bevy::prelude::*;
+mod attack;
+mod damage;
+mod fuel_gauge;
 mod lives_counter;
 mod player;
 
 use std::collections::HashMap;
 
-use crate::states::GameStates;
+use crate::{
+    console::{AddConsoleCommand, ConsoleCommand, ConsoleCommandEvent, PrintToConsoleEvent},
+    run::NewRunStage,
+    states::GameStates,
+};
 
+pub use self::damage::PlayerDamageEvent;
 pub use self::player::Player;
 
 pub struct RunnerPlugin;
@@ -13,17 +21,33 @@ pub struct RunnerPlugin;
 impl Plugin for RunnerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(player::PlayerPlugin);
+        app.add_plugin(attack::AttackPlugin);
+        app.add_plugin(damage::DamagePlugin);
 
         app.add_system_set(
             SystemSet::on_enter(GameStates::Main)
                 .with_system(lives_counter::build_ui)
+                .with_system(fuel_gauge::build_ui)
                 .label("lives_counter_build"),
         );
         app.add_system_set(
             SystemSet::on_update(GameStates::Main)
                 .with_system(lives_counter::update_counter)
+                .with_system(fuel_gauge::update_gauge)
                 .after("lives_counter_build"),
         );
+        app.add_system_set(
+            SystemSet::on_exit(GameStates::Main)
+                .with_system(lives_counter::destroy_ui)
+                .with_system(fuel_gauge::destroy_ui),
+        );
+        app.add_system_to_stage(NewRunStage, reset_collected_chars);
+
+        app.add_console_command(ConsoleCommand::new(
+            "inventory",
+            "print the collected letters",
+        ));
+        app.add_system(inventory_command);
     }
 }
 
@@ -43,3 +67,40 @@ impl CollectedChars {
         }
     }
 }
+
+fn reset_collected_chars(mut collected_chars: ResMut<CollectedChars>) {
+    collected_chars.values.clear();
+    collected_chars.values_map.clear();
+    collected_chars.initialize_map();
+}
+
+fn inventory_command(
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    collected_chars: Res<CollectedChars>,
+) {
+    for _ in command_reader
+        .iter()
+        .filter(|event| event.name == "inventory")
+    {
+        let counts: Vec<String> = LETTERS
+            .iter()
+            .filter_map(|c| match collected_chars.values_map.get(c) {
+                Some(count) if *count > 0 => Some(format!("{}: {}", c, count)),
+                _ => None,
+            })
+            .collect();
+
+        let message = if counts.is_empty() {
+            "No letters collected yet.".to_string()
+        } else {
+            // a few letters per line to fit in the terminal
+            counts
+                .chunks(9)
+                .map(|line| line.join("   "))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        print_to_console.send(PrintToConsoleEvent(message));
+    }
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use crate::enemies::Enemy;
+use crate::run::{NewRunStage, RunSeed};
 use crate::{effects, physics, platforms, states::GameStates};
 use bevy::math::Vec3Swizzles;
 use bevy::{prelude::*};
@@ -10,12 +11,21 @@ use bevy_rapier2d::prelude::*;
 use rand::Rng;
 use std::collections::HashMap;
 
+use super::damage::{self, PlayerDamageEvent};
 use super::CollectedChars;
 use crate::audio::{GameAudioOptions, GameAudioState};
 use crate::cheat_codes::{CheatCodeKind, CheatCodeResource};
+use crate::controls::{Action, ActionInput};
 use crate::interactables::{CharTextComponent, InteractableComponent, InteractableType};
 use crate::toast::ShowToast;
 
+// upward acceleration while flying, in physics units (gravity is 140)
+const FLY_THRUST: f32 = 220.0;
+const FLY_MAX_SPEED: f32 = 30.0;
+// fuel per second, a full tank lasts 2 seconds
+const FUEL_CONSUMPTION_RATE: f32 = 0.5;
+const FUEL_RECHARGE_RATE: f32 = 0.8;
+
 #[derive(Debug, Component)]
 pub struct Player {
     pub speed: f32,
@@ -28,6 +38,21 @@ pub struct Player {
     pub dash_cooldown_timer: Timer,
     pub dash_input_count: u8,
     pub is_dashing: bool,
+    pub facing_left: bool,
+    pub attack_cooldown_timer: Timer,
+    // damage not yet converted to a lost life (reduced by Armor)
+    pub damage_taken: f32,
+    pub has_shield: bool,
+    pub shield_recharge_timer: Timer,
+    pub invincibility_timer: Timer,
+    // between 0 (empty) and 1 (full)
+    pub fuel: f32,
+    pub is_flying: bool,
+    // how long space must be held in the air before flying
+    pub fly_hold_timer: Timer,
+    pub is_crouching: bool,
+    // set by the `god` debug command, hits are ignored
+    pub god_mode: bool,
 }
 
 #[derive(Debug)]
@@ -108,6 +133,10 @@ impl Plugin for PlayerPlugin {
                     length: 8,
                     offset: 32,
                 },
+                fly: AnimationData {
+                    length: 2,
+                    offset: 40,
+                },
                 run_step_counter: 0,
             })
             .add_system_set(
@@ -121,15 +150,18 @@ impl Plugin for PlayerPlugin {
                     .label("player_feet"),
             )
             .add_system_set(SystemSet::on_exit(GameStates::Main).with_system(despawn_character))
+            .add_system_to_stage(NewRunStage, reset_animation)
             .add_system_set(
                 SystemSet::on_update(GameStates::Main)
                     .with_system(follow_player_camera)
                     .with_system(animate_sprite)
                     .with_system(move_character)
+                    .with_system(crouch_character)
                     .after("player_feet")
                     .with_system(detect_char_interactable)
                     .with_system(player_collide_enemy)
                     .with_system(player_fall_damage)
+                    .with_system(enter_game_over)
                     .with_system(detect_cheat_code_activation)
                     .with_system(show_terminal_toaster_notification),
             );
@@ -154,23 +186,34 @@ fn spawn_character(
     rapier_config: Res<RapierConfiguration>,
 ) {
     let texture_handle = asset_server.load("player.png");
-    let texture_atlas = TextureAtlas::from_grid(texture_handle, Vec2::new(71.0, 67.0), 8, 5);
+    let texture_atlas = TextureAtlas::from_grid(texture_handle, Vec2::new(71.0, 67.0), 8, 6);
     let texture_atlas_handle = texture_atlases.add(texture_atlas);
     let player = Player {
-        speed: 8.0,
+        speed: physics::BASE_SPEED_TIER.speed,
         lives: 6,
-        acceleration: 0.12,
-        deceleration: 0.1,
+        acceleration: physics::BASE_SPEED_TIER.acceleration,
+        deceleration: physics::BASE_SPEED_TIER.deceleration,
         feet_touching_platforms: FeetTouchingPlatforms { platforms: vec![] },
         jump_count: 0,
         dash_input_timer: Timer::from_seconds(0.25, false),
         dash_cooldown_timer: Timer::from_seconds(1.5, false),
         dash_input_count: 1,
         is_dashing: false,
+        facing_left: false,
+        attack_cooldown_timer: Timer::from_seconds(0.0, false),
+        damage_taken: 0.0,
+        has_shield: true,
+        shield_recharge_timer: Timer::from_seconds(damage::SHIELD_RECHARGE_TIME, false),
+        invincibility_timer: Timer::from_seconds(0.0, false),
+        fuel: 1.0,
+        is_flying: false,
+        fly_hold_timer: Timer::from_seconds(0.2, false),
+        is_crouching: false,
+        god_mode: false,
     };
 
-    let collider_size_hx = 30.0 / rapier_config.scale / 2.0;
-    let collider_size_hy = 70.0 / rapier_config.scale / 2.0;
+    let collider_size_hx = physics::PLAYER_WIDTH / rapier_config.scale / 2.0;
+    let collider_size_hy = physics::PLAYER_HEIGHT / rapier_config.scale / 2.0;
 
     commands
         .spawn_bundle(RigidBodyBundle {
@@ -210,6 +253,8 @@ fn spawn_character(
                 })
                 .insert(PlayerAnimationTimer(Timer::from_seconds(0.1, true)));
 
+            damage::spawn_shield_bubble(parent, &asset_server);
+
             parent
                 .spawn_bundle(ColliderBundle {
                     shape: ColliderShape::cuboid(
@@ -313,6 +358,7 @@ pub struct PlayerAnimationResource {
     pub jump: AnimationData,
     pub idle: AnimationData,
     pub dash_attack: AnimationData,
+    pub fly: AnimationData,
     pub run_step_counter: u32,
 }
 
@@ -321,6 +367,10 @@ pub struct AnimationData {
     pub offset: usize,
 }
 
+fn reset_animation(mut player_animation_resource: ResMut<PlayerAnimationResource>) {
+    player_animation_resource.run_step_counter = 0;
+}
+
 pub fn animate_sprite(
     time: Res<Time>,
     mut player_animation_resource: ResMut<PlayerAnimationResource>,
@@ -328,6 +378,7 @@ pub fn animate_sprite(
     mut query: Query<(&mut PlayerAnimationTimer, &mut TextureAtlasSprite)>,
     mut game_audio_state: ResMut<GameAudioState>,
     rapier_config: Res<RapierConfiguration>,
+    mut run_seed: ResMut<RunSeed>,
 ) {
     for (mut player, rb_vel) in player_query.iter_mut() {
         for (mut timer, mut sprite) in query.iter_mut() {
@@ -360,6 +411,17 @@ pub fn animate_sprite(
                     {
                         sprite.index += 1;
                     }
+                } else if player.is_flying {
+                    if sprite.index < player_animation_resource.fly.offset
+                        || sprite.index
+                            >= (player_animation_resource.fly.length
+                                + player_animation_resource.fly.offset)
+                    {
+                        sprite.index = player_animation_resource.fly.offset;
+                    } else {
+                        sprite.index = ((sprite.index + 1) % player_animation_resource.fly.length)
+                            + player_animation_resource.fly.offset;
+                    }
                 } else if player.feet_touching_platforms.platforms.is_empty() {
                     // player is jumping
                     if sprite.index < player_animation_resource.jump.offset
@@ -400,7 +462,7 @@ pub fn animate_sprite(
                                         rb_vel.linvel.x.abs()
                                             / (player.speed * rapier_config.scale),
                                     ),
-                                    handle_idx: Some(rand::thread_rng().gen_range(0..10)),
+                                    handle_idx: Some(run_seed.cosmetic_rng.gen_range(0..10)),
                                 },
                             )
                         }
@@ -427,7 +489,7 @@ pub fn animate_sprite(
                                         rb_vel.linvel.x.abs()
                                             / (player.speed * rapier_config.scale),
                                     ),
-                                    handle_idx: Some(rand::thread_rng().gen_range(0..10)),
+                                    handle_idx: Some(run_seed.cosmetic_rng.gen_range(0..10)),
                                 },
                             )
                         }
@@ -452,7 +514,7 @@ pub fn animate_sprite(
 }
 
 fn move_character(
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: ActionInput,
     rapier_config: Res<RapierConfiguration>,
     mut query: Query<(
         &mut Player,
@@ -467,42 +529,72 @@ fn move_character(
 ) {
     for (mut player, mut rb_vel, rb_mprops) in query.iter_mut() {
         // update acceleration value
-        if cheat_codes.is_code_activated(&CheatCodeKind::SpeedBoost3) {
-            player.acceleration = 0.15;
-            player.deceleration = 0.4;
-            player.speed = 8.9;
-        } else if cheat_codes.is_code_activated(&CheatCodeKind::SpeedBoost2) {
-            player.acceleration = 0.14;
-            player.deceleration = 0.3;
-            player.speed = 8.6;
-        } else if cheat_codes.is_code_activated(&CheatCodeKind::SpeedBoost1) {
-            player.acceleration = 0.13;
-            player.deceleration = 0.2;
-            player.speed = 8.3;
-        }
+        let tier = physics::speed_tier(cheat_codes.activated());
+        player.acceleration = tier.acceleration;
+        player.deceleration = tier.deceleration;
+        player.speed = tier.speed;
 
-        let _up = keyboard_input.pressed(KeyCode::W);
-        let _down = keyboard_input.pressed(KeyCode::S);
-        let right = keyboard_input.pressed(KeyCode::D);
-        let dash = keyboard_input.just_released(KeyCode::D);
+        let dash = actions.just_released(Action::MoveRight);
 
-        let jump = cheat_codes.is_code_activated(&CheatCodeKind::Jump)
-            && keyboard_input.just_released(KeyCode::Space)
+        let mut jump = cheat_codes.is_code_activated(&CheatCodeKind::Jump)
+            && actions.just_released(Action::Jump)
             && !player.feet_touching_platforms.platforms.is_empty()
             || (cheat_codes.is_code_activated(&CheatCodeKind::DoubleJump)
-                && keyboard_input.just_released(KeyCode::Space));
+                && actions.just_released(Action::Jump));
+
+        let on_ground = !player.feet_touching_platforms.platforms.is_empty();
+        if cheat_codes.is_code_activated(&CheatCodeKind::Fly) {
+            if on_ground {
+                player.is_flying = false;
+                player.fuel = (player.fuel + FUEL_RECHARGE_RATE * time.delta_seconds()).min(1.0);
+            }
 
-        let left = cheat_codes.is_code_activated(&CheatCodeKind::MoveLeft)
-            && keyboard_input.pressed(KeyCode::A);
+            if actions.pressed(Action::Jump) && !on_ground {
+                player.fly_hold_timer.tick(time.delta());
+                if player.fly_hold_timer.finished() && player.fuel > 0.0 {
+                    player.is_flying = true;
+                }
+            } else {
+                // releasing space after flying must not trigger a jump
+                if player.fly_hold_timer.finished() {
+                    jump = false;
+                }
+                player.is_flying = false;
+                player.fly_hold_timer.reset();
+            }
 
-        let x_axis = -(left as i8) + right as i8;
+            if player.is_flying {
+                player.fuel = (player.fuel - FUEL_CONSUMPTION_RATE * time.delta_seconds()).max(0.0);
+                if player.fuel <= 0.0 {
+                    player.is_flying = false;
+                }
+                rb_vel.linvel.y =
+                    (rb_vel.linvel.y + FLY_THRUST * time.delta_seconds()).min(FLY_MAX_SPEED);
+            }
+        }
+
+        // analog stick values scale the acceleration and the top speed
+        let mut x_axis = actions.move_axis();
+        if !cheat_codes.is_code_activated(&CheatCodeKind::MoveLeft) {
+            x_axis = x_axis.max(0.0);
+        }
+        if x_axis != 0.0 {
+            player.facing_left = x_axis < 0.0;
+        }
 
-        if dash && cheat_codes.is_code_activated(&CheatCodeKind::Dash) {
+        if cheat_codes.is_code_activated(&CheatCodeKind::Dash)
+            && actions.just_pressed(Action::Dash)
+            && player.dash_cooldown_timer.finished()
+        {
+            rb_vel.apply_impulse(rb_mprops, Vec2::new(physics::DASH_IMPULSE, 0.0).into());
+            player.is_dashing = true;
+            player.dash_cooldown_timer.reset()
+        } else if dash && cheat_codes.is_code_activated(&CheatCodeKind::Dash) {
             if player.dash_input_count == 0 {
                 player.dash_input_count = 1;
                 player.dash_input_timer.reset();
             } else if player.dash_input_count == 1 && player.dash_cooldown_timer.finished() {
-                rb_vel.apply_impulse(rb_mprops, Vec2::new(1000.0, 0.0).into());
+                rb_vel.apply_impulse(rb_mprops, Vec2::new(physics::DASH_IMPULSE, 0.0).into());
                 player.is_dashing = true;
                 player.dash_cooldown_timer.reset()
             }
@@ -518,12 +610,11 @@ fn move_character(
             //decrease dash cooldown
             player.dash_cooldown_timer.tick(time.delta());
 
-            if x_axis != 0 {
-                rb_vel.linvel.x += player.acceleration * (x_axis as f32) * rapier_config.scale;
-                if rb_vel.linvel.x.abs() > player.speed * rapier_config.scale {
-                    rb_vel.linvel.x = (rb_vel.linvel.x / rb_vel.linvel.x.abs())
-                        * player.speed
-                        * rapier_config.scale;
+            if x_axis != 0.0 {
+                rb_vel.linvel.x += player.acceleration * x_axis * rapier_config.scale;
+                let max_speed = player.speed * x_axis.abs() * rapier_config.scale;
+                if rb_vel.linvel.x.abs() > max_speed {
+                    rb_vel.linvel.x = (rb_vel.linvel.x / rb_vel.linvel.x.abs()) * max_speed;
                 }
             } else if rb_vel.linvel.x.abs() > 0.4 {
                 // decelerate
@@ -541,7 +632,7 @@ fn move_character(
         if jump {
             if !player.feet_touching_platforms.platforms.is_empty() {
                 // single jump
-                physics::jump(1500.0, &mut rb_vel, rb_mprops);
+                physics::jump(physics::JUMP_IMPULSE, &mut rb_vel, rb_mprops);
                 if cheat_codes.is_code_activated(&CheatCodeKind::DoubleJump) {
                     player.jump_count = 1;
                 } else {
@@ -550,7 +641,7 @@ fn move_character(
             } else if player.jump_count == 1 {
                 // double jump
                 rb_vel.linvel.y = 0.0;
-                physics::jump(1500.0, &mut rb_vel, rb_mprops);
+                physics::jump(physics::JUMP_IMPULSE, &mut rb_vel, rb_mprops);
                 for mut sprite in animation_query.iter_mut() {
                     sprite.index = player_animation_resource.jump.offset;
                 }
@@ -566,6 +657,43 @@ fn move_character(
     }
 }
 
+/// Halve the player's collider while crouching so it fits under low ceilings
+fn crouch_character(
+    actions: ActionInput,
+    rapier_config: Res<RapierConfiguration>,
+    cheat_codes: Res<CheatCodeResource>,
+    mut player_query: Query<(&mut Player, &mut ColliderShapeComponent)>,
+    mut sprite_query: Query<&mut Transform, With<PlayerAnimationTimer>>,
+) {
+    let crouch =
+        cheat_codes.is_code_activated(&CheatCodeKind::Crouch) && actions.pressed(Action::Crouch);
+
+    for (mut player, mut collider_shape) in player_query.iter_mut() {
+        if player.is_crouching == crouch {
+            continue;
+        }
+        player.is_crouching = crouch;
+
+        let collider_size_hx = physics::PLAYER_WIDTH / rapier_config.scale / 2.0;
+        let collider_size_hy = if crouch {
+            physics::PLAYER_HEIGHT / 2.0 / rapier_config.scale / 2.0
+        } else {
+            physics::PLAYER_HEIGHT / rapier_config.scale / 2.0
+        };
+        *collider_shape = ColliderShape::cuboid(collider_size_hx, collider_size_hy).into();
+
+        for mut transform in sprite_query.iter_mut() {
+            if crouch {
+                transform.scale.y = 1.0;
+                transform.translation.y = -4.0;
+            } else {
+                transform.scale.y = 1.5;
+                transform.translation.y = 12.0;
+            }
+        }
+    }
+}
+
 fn follow_player_camera(
     player: Query<&Transform, With<Player>>,
     camera: Query<&Transform, (With<ParallaxCameraComponent>, Without<Player>)>,
@@ -639,38 +767,46 @@ pub struct GameOverEvent;
 pub fn player_fall_damage(
     mut player_query: Query<(&mut Player, &Transform)>,
     mut game_over_event: EventWriter<GameOverEvent>,
-    mut game_state: ResMut<State<GameStates>>,
 ) {
     for (mut player, transform) in player_query.iter_mut() {
-        if transform.translation.y < -400.0 {
+        // like hits, falls are ignored in god mode
+        if transform.translation.y < -400.0 && !player.god_mode {
             player.lives = 0;
             game_over_event.send(GameOverEvent);
             info!("Fell down hole");
-            game_state.push(GameStates::GameOver).unwrap();
         }
     }
 }
 
+/// Push the game over state once the player is out of lives, checked here only so a
+/// hit and a fall in the same frame don't push it twice. The dev-console shortcut in
+/// `game_over` pushes it too.
+fn enter_game_over(player_query: Query<&Player>, mut game_state: ResMut<State<GameStates>>) {
+    if player_query.iter().any(|player| player.lives <= 0) {
+        // tried again next frame when another transition is already queued
+        let _ = game_state.push(GameStates::GameOver);
+    }
+}
+
 pub fn player_collide_enemy(
     mut commands: Commands,
-    mut player_query: Query<(Entity, &mut Player)>,
+    player_query: Query<(Entity, &Player)>,
     enemy_query: Query<(Entity, &Transform), With<Enemy>>,
     mut contact_events: EventReader<ContactEvent>,
-    mut game_over_event: EventWriter<GameOverEvent>,
+    mut damage_writer: EventWriter<PlayerDamageEvent>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     asset_server: Res<AssetServer>,
-    mut game_state: ResMut<State<GameStates>>,
     mut game_audio_state: ResMut<GameAudioState>,
 ) {
     for contact_event in contact_events.iter() {
         if let ContactEvent::Started(h1, h2) = contact_event {
-            for (player_entity, mut player) in player_query.iter_mut() {
+            for (player_entity, player) in player_query.iter() {
                 for (enemy_entity, enemy_transform) in enemy_query.iter() {
                     if h1.entity() == player_entity && h2.entity() == enemy_entity
                         || h2.entity() == player_entity && h1.entity() == enemy_entity
                     {
                         if !player.is_dashing {
-                            player.lives -= 1;
+                            damage_writer.send(PlayerDamageEvent { amount: 1.0 });
                         }
                         commands.entity(enemy_entity).despawn();
                         // spawn explostion
@@ -686,10 +822,6 @@ pub fn player_collide_enemy(
                                 ..Default::default()
                             },
                         );
-                        if player.lives <= 0 {
-                            game_over_event.send(GameOverEvent);
-                            game_state.push(GameStates::GameOver).unwrap();
-                        }
                     }
                 }
             }
@@ -713,17 +845,20 @@ pub fn detect_cheat_code_activation(
 fn show_terminal_toaster_notification(
     player_query: Query<&Transform, With<Player>>,
     mut toast_writer: EventWriter<ShowToast>,
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: ActionInput,
 ) {
-    let right = keyboard_input.just_released(KeyCode::D);
-    let left = keyboard_input.just_released(KeyCode::A);
+    let right = actions.just_released(Action::MoveRight);
+    let left = actions.just_released(Action::MoveLeft);
 
     if let Some(player_transform) = player_query.iter().next() {
         if (right || left)
             && player_transform.translation.x > 1150.
             && player_transform.translation.x <= 1300.
         {
-            let value = String::from("Press E to access console");
+            let value = format!(
+                "Press {} to access console",
+                actions.prompt_name(Action::Interact)
+            );
             toast_writer.send(ShowToast {
                 value,
                 duration: Duration::from_secs(3),
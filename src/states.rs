@@ -10,4 +10,8 @@ pub enum GameStates {
     TabMenu,
     GameOver,
     MainMenu,
+    HighScores,
+    Controls,
+    #[cfg(debug_assertions)]
+    ChunkEditor,
 }
@@ -1,7 +1,13 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::RigidBodyVelocityComponent;
 
-use crate::{cheat_codes::CheatCodeResource, runner::Player, states::GameStates};
+use crate::{
+    cheat_codes::CheatCodeResource,
+    console::{AddConsoleCommand, ConsoleCommand, ConsoleCommandEvent, PrintToConsoleEvent},
+    run::NewRunStage,
+    runner::Player,
+    states::GameStates,
+};
 
 pub struct GameStatsPlugin;
 
@@ -9,9 +15,15 @@ impl Plugin for GameStatsPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<EnemyKilledEvent>();
         app.insert_resource(GameStatsResource::new());
+        app.add_system_to_stage(NewRunStage, reset_stats);
         app.add_system(enemy_killed_handler);
         app.add_system(update_max_distance);
         app.add_system(update_cheats_activated);
+        app.add_console_command(ConsoleCommand::new(
+            "status",
+            "print the remaining lives, distance and score",
+        ));
+        app.add_system(status_command);
         app.add_system_set(SystemSet::on_update(GameStates::Main).with_system(update_run_time));
         app.add_system_set(SystemSet::on_update(GameStates::Main).with_system(update_avg_speed));
     }
@@ -51,7 +63,11 @@ impl GameStatsResource {
     }
 }
 
-pub struct EnemyKilledEvent(usize);
+pub struct EnemyKilledEvent(pub usize);
+
+fn reset_stats(mut stats_res: ResMut<GameStatsResource>) {
+    *stats_res = GameStatsResource::new();
+}
 
 pub fn enemy_killed_handler(
     mut enemy_event_reader: EventReader<EnemyKilledEvent>,
@@ -104,3 +120,24 @@ pub fn update_avg_speed(
     stats_res.avg_speed = (avg_dist + velocity.x * (time.delta_seconds_f64() as f32))
         / (stats_res.run_time as f32 + time.delta_seconds_f64() as f32);
 }
+
+fn status_command(
+    mut command_reader: EventReader<ConsoleCommandEvent>,
+    mut print_to_console: EventWriter<PrintToConsoleEvent>,
+    stats_res: Res<GameStatsResource>,
+    player_query: Query<&Player>,
+) {
+    for _ in command_reader.iter().filter(|event| event.name == "status") {
+        let lives = match player_query.get_single() {
+            Ok(player) => player.lives.to_string(),
+            Err(_) => "?".to_string(),
+        };
+        print_to_console.send(PrintToConsoleEvent(format!(
+            "Remaining lives: {}\nDistance: {:.1}\nEnemies killed: {}\nScore: {}",
+            lives,
+            stats_res.distance,
+            stats_res.enemy_killed,
+            stats_res.get_score()
+        )));
+    }
+}
@@ -8,6 +8,7 @@ mod second_page;
 
 use crate::{
     cheat_codes::{CheatCodeKind, CheatCodeResource},
+    controls::{Action, ActionInput},
     runner::CollectedChars,
     states::GameStates,
     stats::GameStatsResource,
@@ -63,14 +64,14 @@ pub struct TabMenuContent;
 fn switch_page(
     commands: Commands,
     mut assets: ResMut<TabMenuAssets>,
-    mut keyboard: ResMut<Input<KeyCode>>,
+    mut actions: ActionInput,
     query: Query<Entity, With<TabMenuContent>>,
     window: Res<Windows>,
     cheat_codes_res: Res<CheatCodeResource>,
     collected_chars_res: Res<CollectedChars>,
     stats_res: Res<GameStatsResource>,
 ) {
-    if keyboard.just_pressed(KeyCode::Tab) {
+    if actions.just_pressed(Action::TabMenu) {
         // on the first page
         if assets.current_page == "first".to_string() {
             assets.current_page = "second".to_string();
@@ -87,7 +88,7 @@ fn switch_page(
             first_page::build_ui(commands, assets, query, cheat_codes_res, stats_res, window);
         }
 
-        keyboard.reset(KeyCode::Tab);
+        actions.reset(Action::TabMenu);
     }
 }
 
@@ -97,30 +98,27 @@ fn destroy_menu(mut commands: Commands, query: Query<Entity, With<TabMenuCompone
     }
 }
 
-fn open_menu_trigger(
-    mut keyboard: ResMut<Input<KeyCode>>,
-    mut game_state: ResMut<State<GameStates>>,
-) {
-    if keyboard.just_pressed(KeyCode::Tab) {
+fn open_menu_trigger(mut actions: ActionInput, mut game_state: ResMut<State<GameStates>>) {
+    if actions.just_pressed(Action::TabMenu) {
         game_state.push(GameStates::TabMenuLoading).unwrap();
-        keyboard.reset(KeyCode::Tab);
+        actions.reset(Action::TabMenu);
     }
 }
 
-fn close_menu_trigger(
-    mut keyboard: ResMut<Input<KeyCode>>,
-    mut game_state: ResMut<State<GameStates>>,
-) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+fn close_menu_trigger(mut actions: ActionInput, mut game_state: ResMut<State<GameStates>>) {
+    if actions.just_pressed(Action::Pause) {
         game_state.pop().unwrap();
-        keyboard.reset(KeyCode::Tab);
-        keyboard.reset(KeyCode::Escape);
+        actions.reset(Action::TabMenu);
+        actions.reset(Action::Pause);
     }
 }
 
-fn remind_second_page(mut ev_writer: EventWriter<ShowToast>) {
+fn remind_second_page(mut ev_writer: EventWriter<ShowToast>, actions: ActionInput) {
     ev_writer.send(ShowToast {
-        value: "Press TAB to turn page".to_string(),
+        value: format!(
+            "Press {} to turn page",
+            actions.prompt_name(Action::TabMenu)
+        ),
         duration: Duration::from_secs(2),
     });
 }
@@ -7,7 +7,10 @@ use bevy_tweening::{
     TweeningType,
 };
 
+#[cfg(feature = "dev-console")]
 use crate::cheat_codes::CheatCodeResource;
+use crate::run::NewRunStage;
+#[cfg(feature = "dev-console")]
 use crate::runner::CollectedChars;
 
 pub struct ShowToast {
@@ -30,16 +33,21 @@ impl Plugin for ToastPlugin {
     fn build(&self, app: &mut App) {
         // always here so no need for a systemset
         app.add_startup_system(build_ui);
-        app.add_system(update_content)
-            .add_system(test)
-            .add_system(display_queue);
+        app.add_system(update_content).add_system(display_queue);
+        #[cfg(feature = "dev-console")]
+        app.add_system(test);
         app.add_event::<ShowToast>();
         app.add_plugin(NinePatchPlugin::<()>::default());
         app.add_plugin(TweeningPlugin);
         app.insert_resource(ToastQueueResource { queue: Vec::new() });
+        app.add_system_to_stage(NewRunStage, clear_queue);
     }
 }
 
+fn clear_queue(mut toast_queue: ResMut<ToastQueueResource>) {
+    toast_queue.queue.clear();
+}
+
 fn get_toast_animation(duration: Duration) -> Sequence<Style> {
     let close_animation = Tween::new(
         EaseFunction::CubicInOut,
@@ -189,6 +197,7 @@ fn display_queue(
     }
 }
 
+#[cfg(feature = "dev-console")]
 fn test(
     keyboard: Res<Input<KeyCode>>,
     mut toast_writer: EventWriter<ShowToast>,